@@ -1,9 +1,11 @@
 #![allow(unused_must_use)]
 #![allow(dead_code)]
 
-extern crate gcc;
+extern crate cc;
+#[cfg(feature = "system-lib")]
+extern crate pkg_config;
 
-const LIBHOEDOWN_SRC: &'static [&'static str] = &[
+const LIBHOEDOWN_SRC: &[&str] = &[
   "libhoedown/src/autolink.c",
   "libhoedown/src/buffer.c",
   "libhoedown/src/document.c",
@@ -15,6 +17,145 @@ const LIBHOEDOWN_SRC: &'static [&'static str] = &[
   "libhoedown/src/version.c",
 ];
 
+/// How much of a struct's layout in `src/ffi.rs` is checked against the linked library
+enum Layout {
+  /// Every field is pointer-sized, and the struct is passed by value
+  Pointers,
+
+  /// The fields are a pointer-sized prefix of the struct, which is only used by reference
+  PointerPrefix,
+
+  /// The fields are in the same order
+  Ordered,
+}
+
+/// The structs declared in `src/ffi.rs` whose layout is checked against the linked library's
+const LAYOUT_STRUCTS: &[(&str, Layout)] = &[
+  ("hoedown_renderer", Layout::Pointers),
+  ("hoedown_buffer", Layout::PointerPrefix),
+  ("hoedown_html_renderer_state", Layout::Ordered),
+];
+
+/// The names of a struct's fields in `src/ffi.rs`, in order
+fn fields(ffi: &str, name: &str) -> Vec<String> {
+  let declaration = format!("pub struct {} {{", name);
+
+  ffi.lines()
+    .skip_while(|line| !line.starts_with(&declaration))
+    .skip(1)
+    .take_while(|line| !line.starts_with("}"))
+    .filter_map(|line| {
+      let line = line.trim();
+      let line = line.strip_prefix("pub ").unwrap_or(line);
+
+      line.find(':').map(|colon| String::from(&line[.. colon]))
+    })
+    .collect()
+}
+
+/// The major version of libhoedown that the bindings are written for, from
+/// the `VERSION_MAJOR` constant in `src/version.rs`
+fn version_major(version: &str) -> i32 {
+  version.lines()
+    .filter_map(|line| line.trim().strip_prefix("pub const VERSION_MAJOR: i32 = "))
+    .filter_map(|value| value.trim_end_matches(';').parse().ok())
+    .next()
+    .expect("src/version.rs should define VERSION_MAJOR")
+}
+
+/// C code that only compiles if the headers agree with `src/ffi.rs`
+///
+/// This checks the library's major version, that each field in `src/ffi.rs`
+/// exists under the same name and in the same order, and for the structs made
+/// of pointers, that each field is at the offset of a pointer-sized field. The
+/// fields' types and sizes aren't checked otherwise, nor are function signatures.
+fn layout_check(ffi: &str, major: i32) -> String {
+  let mut check = format!("\
+#include <stddef.h>
+#include <hoedown/buffer.h>
+#include <hoedown/document.h>
+#include <hoedown/html.h>
+#include <hoedown/version.h>
+
+#define LAYOUT_CHECK(name, condition) typedef char layout_check_##name[(condition) ? 1 : -1];
+
+LAYOUT_CHECK(version, HOEDOWN_VERSION_MAJOR == {})
+", major);
+
+  for &(name, ref layout) in LAYOUT_STRUCTS {
+    let fields = fields(ffi, name);
+
+    for (i, field) in fields.iter().enumerate() {
+      // referencing each field checks its name, and comparing offsets checks the order
+      if let Layout::Ordered = *layout {
+        if i > 0 {
+          check.push_str(&format!(
+            "LAYOUT_CHECK({0}_{1}, offsetof({0}, {1}) > offsetof({0}, {2}))\n", name, field, fields[i - 1]));
+        }
+      } else {
+        check.push_str(&format!(
+          "LAYOUT_CHECK({0}_{1}, offsetof({0}, {1}) == {2} * sizeof(void *))\n", name, field, i));
+      }
+    }
+
+    if let Layout::Pointers = *layout {
+      check.push_str(&format!(
+        "LAYOUT_CHECK({0}_sizeof, sizeof({0}) == {1} * sizeof(void *))\n", name, fields.len()));
+    }
+  }
+
+  check
+}
+
+#[cfg(feature = "system-lib")]
+fn main() {
+  use std::env;
+  use std::fs::File;
+  use std::io::{self, Read, Write};
+  use std::path::Path;
+  use std::process;
+
+  println!("cargo:rerun-if-changed=src/ffi.rs");
+  println!("cargo:rerun-if-changed=src/version.rs");
+
+  let mut version = String::new();
+  File::open("src/version.rs").unwrap().read_to_string(&mut version).unwrap();
+  let major = version_major(&version);
+
+  let probe =
+    pkg_config::Config::new()
+    .atleast_version(&format!("{}.0", major))
+    .probe("hoedown");
+
+  let library = match probe {
+    Ok(library) => library,
+    Err(error) => {
+      let _ = writeln!(io::stderr(),
+                       "the `system-lib` feature requires libhoedown {}.x, found with pkg-config: {}",
+                       major, error);
+      process::exit(1);
+    },
+  };
+
+  let mut ffi = String::new();
+  File::open("src/ffi.rs").unwrap().read_to_string(&mut ffi).unwrap();
+
+  let out_dir = env::var("OUT_DIR").unwrap();
+  let path = Path::new(&out_dir).join("layout_check.c");
+  File::create(&path).unwrap().write_all(layout_check(&ffi, major).as_bytes()).unwrap();
+
+  // the check fails to compile, and so fails the build, on any mismatch
+  let mut build = cc::Build::new();
+  build.file(&path);
+
+  for include in &library.include_paths {
+    build.include(include);
+  }
+
+  build.compile("hoedown_layout_check");
+}
+
+#[cfg(not(feature = "system-lib"))]
 fn main() {
-  gcc::compile_library("libhoedown.a", LIBHOEDOWN_SRC);
+  cc::Build::new().files(LIBHOEDOWN_SRC).compile("hoedown");
 }
@@ -0,0 +1,222 @@
+//! Derive macro for hoedown renderer wrappers
+//!
+//! This is used through the `derive` feature of the `hoedown` crate, which
+//! re-exports its macros.
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as Tokens;
+use syn::{Data, DeriveInput, Error, GenericParam, ImplItem, ItemImpl, Member, Type};
+use syn::spanned::Spanned;
+
+/// Derive `Render` for a wrapper of the field marked `#[render(base)]`
+///
+/// Every callback is rendered by the base renderer, except those overridden
+/// in an optional `impl` block with the `overrides` attribute. That block
+/// becomes the type's `Wrapper` implementation, so it may only contain
+/// callbacks, each with the signature of the `Wrapper` method of the same
+/// name, and `self.base()` returns the base renderer. Helper methods belong
+/// in a separate `impl` block.
+///
+///``` rust
+///# extern crate hoedown;
+///# use hoedown::{Markdown, Render, Buffer, Html, overrides};
+///# use hoedown::renderer::html::Flags;
+///# use std::io::Write;
+///#[derive(Render)]
+///struct Strike {
+///    #[render(base)]
+///    html: Html,
+///}
+///
+///#[overrides]
+///impl Strike {
+///    fn emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+///        output.write_all(b"<s>").unwrap();
+///        content.map(|c| output.pipe(c));
+///        output.write_all(b"</s>").unwrap();
+///        true
+///    }
+///}
+///
+///let doc = Markdown::new("*old* **new**");
+///let mut strike = Strike { html: Html::new(Flags::empty(), 0) };
+///
+///assert_eq!(strike.render(&doc).to_str().unwrap(), "<p><s>old</s> <strong>new</strong></p>\n");
+///```
+#[proc_macro_derive(Render, attributes(render))]
+pub fn derive_render(input: TokenStream) -> TokenStream {
+    let result = syn::parse::<DeriveInput>(input).and_then(|input| render(&input));
+
+    match result {
+        Ok(tokens) => tokens.into(),
+        Err(error) => compile_error(error),
+    }
+}
+
+/// Implement `Wrapper` with the callbacks of an inherent `impl` block of a
+/// type deriving `Render`
+///
+/// See `derive(Render)`.
+#[proc_macro_attribute]
+pub fn overrides(args: TokenStream, input: TokenStream) -> TokenStream {
+    let result = if args.is_empty() {
+        syn::parse::<ItemImpl>(input).and_then(|item| wrapper(&item))
+    } else {
+        Err(Error::new_spanned(Tokens::from(args), "`overrides` takes no arguments"))
+    };
+
+    match result {
+        Ok(tokens) => tokens.into(),
+        Err(error) => compile_error(error),
+    }
+}
+
+/// The callbacks which an `overrides` block may contain
+const CALLBACKS: &[&str] = &[
+    "code_block", "quote_block", "header", "header_with_attributes",
+    "horizontal_rule", "list", "list_item", "paragraph", "table",
+    "table_header", "table_body", "table_row", "table_cell", "footnotes",
+    "footnote_definition", "html_block", "positioned_block",
+    "definition_list", "definition_term", "definition_data", "admonition",
+    "autolink", "code_span", "double_emphasis", "emphasis", "underline",
+    "highlight", "quote_span", "image", "line_break", "link", "wiki_link",
+    "pattern_link", "triple_emphasis", "strikethrough", "superscript",
+    "footnote_reference", "math", "html_span", "entity", "normal_text",
+    "before_render", "after_render",
+];
+
+/// Report the error as a compile error
+///
+/// `Error::to_compile_error` refers to `::core`, which 2015 edition crates
+/// can't name.
+fn compile_error(error: Error) -> TokenStream {
+    let errors = error.into_iter().map(|error| {
+        let message = error.to_string();
+        quote_spanned!(error.span()=> compile_error!(#message);)
+    });
+
+    quote!(#(#errors)*).into()
+}
+
+/// The field marked `#[render(base)]` and its type
+fn base(input: &DeriveInput) -> Result<(Member, &Type), Error> {
+    let fields = match input.data {
+        Data::Struct(ref data) => &data.fields,
+        _ => return Err(Error::new(input.ident.span(), "`Render` can only be derived for structs")),
+    };
+
+    let mut base = None;
+
+    for (i, field) in fields.iter().enumerate() {
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("render")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("base") {
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `base`"))
+                }
+            })?;
+
+            if base.is_some() {
+                return Err(Error::new_spanned(attr, "only one field may be marked `#[render(base)]`"));
+            }
+
+            let member = match field.ident {
+                Some(ref ident) => Member::Named(ident.clone()),
+                None => Member::from(i),
+            };
+
+            base = Some((member, &field.ty));
+        }
+    }
+
+    base.ok_or_else(|| Error::new(input.ident.span(), "`Render` requires a field marked `#[render(base)]` as the base renderer"))
+}
+
+fn render(input: &DeriveInput) -> Result<Tokens, Error> {
+    let (member, ty) = base(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // `wrap!` takes the generics without their brackets or defaults
+    let params = input.generics.params.iter().map(|param| {
+        let mut param = param.clone();
+
+        match param {
+            GenericParam::Type(ref mut param) => {
+                param.eq_token = None;
+                param.default = None;
+            },
+            GenericParam::Const(ref mut param) => {
+                param.eq_token = None;
+                param.default = None;
+            },
+            GenericParam::Lifetime(_) => (),
+        }
+
+        param
+    });
+
+    let predicates = where_clause.map(|clause| &clause.predicates);
+    let base_type = quote_spanned!(ty.span()=> type Base = #ty;);
+
+    Ok(quote! {
+        impl #impl_generics ::hoedown::renderer::wrapper::WrapperBase for #name #ty_generics #where_clause {
+            #base_type
+
+            #[inline(always)]
+            fn base_field(&mut self) -> &mut #ty {
+                &mut self.#member
+            }
+        }
+
+        ::hoedown::wrap!(@derived [#(#params),*] #name #ty_generics where [#predicates]);
+    })
+}
+
+fn wrapper(item: &ItemImpl) -> Result<Tokens, Error> {
+    if let Some((_, ref path, _)) = item.trait_ {
+        return Err(Error::new_spanned(path, "`overrides` must be applied to an inherent `impl` block, not a trait implementation"));
+    }
+
+    for impl_item in &item.items {
+        match *impl_item {
+            ImplItem::Fn(ref method) if CALLBACKS.contains(&&*method.sig.ident.to_string()) => (),
+            ImplItem::Fn(ref method) => {
+                let message = format!("`{}` is not a `Render` callback; helper methods belong in a separate `impl` block", method.sig.ident);
+                return Err(Error::new_spanned(&method.sig.ident, message));
+            },
+            _ => return Err(Error::new_spanned(impl_item, "an `overrides` block may only contain `Render` callbacks")),
+        }
+    }
+
+    let attrs = &item.attrs;
+    let self_ty = &item.self_ty;
+    let items = &item.items;
+    let (impl_generics, _, where_clause) = item.generics.split_for_impl();
+
+    // the base is only known to the type's `derive(Render)`
+    let base_type = quote_spanned! {self_ty.span()=>
+        type Base = <Self as ::hoedown::renderer::wrapper::WrapperBase>::Base;
+    };
+
+    Ok(quote! {
+        #(#attrs)*
+        impl #impl_generics ::hoedown::renderer::wrapper::Wrapper for #self_ty #where_clause {
+            #base_type
+
+            #[inline(always)]
+            fn base(&mut self) -> &mut Self::Base {
+                ::hoedown::renderer::wrapper::WrapperBase::base_field(self)
+            }
+
+            #(#items)*
+        }
+    })
+}
@@ -0,0 +1,499 @@
+//! Autolinking of plain text
+//!
+//! Exposes hoedown's url, email and `www.` detectors outside of document
+//! rendering, so that text which isn't markdown, such as chat or commit
+//! messages, is linked by the same rules as the `AUTOLINK` extension.
+//!
+//! Other references, such as issue numbers, mentions or commit hashes, can be
+//! linked within rendered documents by the `Patterns` wrapper.
+//!
+//!``` rust
+//!# use hoedown::autolink;
+//!let text = "Fixed, see https://example.com/issues/12.";
+//!let links: Vec<_> = autolink::find_links(text).collect();
+//!
+//!assert_eq!(&text[links[0].0.clone()], "https://example.com/issues/12");
+//!assert_eq!(
+//!    autolink::linkify("mail me@example.com"),
+//!    "mail <a href=\"mailto:me@example.com\">me@example.com</a>");
+//!```
+//!
+//!``` rust
+//!# use hoedown::{Markdown, Render, Html};
+//!# use hoedown::renderer::html::Flags;
+//!# use hoedown::autolink::Patterns;
+//!let doc = Markdown::new("Fixes #12, but not `#13`.");
+//!let mut html = Patterns::new(Html::new(Flags::empty(), 0)).issues("https://example.com/issues/");
+//!
+//!assert_eq!(
+//!    html.render(&doc).to_str().unwrap(),
+//!    "<p>Fixes <a href=\"https://example.com/issues/12\">#12</a>, but not <code>#13</code>.</p>\n");
+//!```
+
+use std::io::Write;
+use std::mem;
+use std::ops::Range;
+use std::str;
+
+use libc::{c_uint, size_t};
+
+use buffer::Buffer;
+use escape;
+use ffi::{hoedown_buffer, hoedown_autolink__www, hoedown_autolink__email, hoedown_autolink__url};
+use renderer::{Render, Attributes, AutoLink, Table, list};
+use renderer::wrapper::Wrapper;
+
+/// `HOEDOWN_AUTOLINK_SHORT_DOMAINS`, which hoedown only uses for `www.` links
+const SHORT_DOMAINS: c_uint = 1 << 0;
+
+type Detector = unsafe extern "C" fn(*mut size_t, *mut hoedown_buffer, *const u8, size_t, size_t, c_uint) -> size_t;
+
+/// Iterator over the links in a text, created by `find_links`
+pub struct FindLinks<'a> {
+    text: &'a [u8],
+    position: usize,
+    last_end: usize,
+    link: Buffer,
+}
+
+impl<'a> Iterator for FindLinks<'a> {
+    type Item = (Range<usize>, AutoLink);
+
+    fn next(&mut self) -> Option<(Range<usize>, AutoLink)> {
+        while self.position < self.text.len() {
+            let i = self.position;
+            self.position += 1;
+
+            // the characters that trigger each detector, as in hoedown's document parser
+            let (detector, kind, flags): (Detector, _, _) = match self.text[i] {
+                b'w' => (hoedown_autolink__www, AutoLink::Normal, SHORT_DOMAINS),
+                b':' => (hoedown_autolink__url, AutoLink::Normal, 0),
+                b'@' => (hoedown_autolink__email, AutoLink::Email, 0),
+                _ => continue,
+            };
+
+            let mut rewind: size_t = 0;
+            self.link.truncate(0);
+
+            // the detectors may only look back as far as the end of the previous link
+            let end = unsafe {
+                detector(
+                    &mut rewind,
+                    self.link.as_mut(),
+                    self.text.as_ptr().offset(i as isize),
+                    i - self.last_end,
+                    self.text.len() - i,
+                    flags)
+            };
+
+            if end > 0 {
+                let range = i - rewind .. i + end;
+                self.position = range.end;
+                self.last_end = range.end;
+
+                return Some((range, kind));
+            }
+        }
+
+        None
+    }
+}
+
+/// Find the urls, emails and `www.` links in the text
+///
+/// Each link is given as its byte range within the text, along with its type.
+/// `www.` links are of the `Normal` type, though they have no scheme.
+pub fn find_links<'a>(text: &'a str) -> FindLinks<'a> {
+    FindLinks {
+        text: text.as_bytes(),
+        position: 0,
+        last_end: 0,
+        link: Buffer::new(64),
+    }
+}
+
+/// Escape the text as html, turning its links into anchors
+///
+/// Links are given the `href` that the `Html` renderer would give them: emails
+/// are prefixed with `mailto:` and `www.` links with `http://`.
+pub fn linkify(text: &str) -> String {
+    let mut output = Buffer::new(text.len() + 64);
+    let mut last = 0;
+
+    for (range, kind) in find_links(text) {
+        let link = &text[range.clone()];
+
+        escape::html(text[last .. range.start].as_bytes(), &mut output, false);
+        output.write_all(b"<a href=\"").unwrap();
+
+        if kind == AutoLink::Email {
+            output.write_all(b"mailto:").unwrap();
+        } else if link.starts_with("www.") {
+            output.write_all(b"http://").unwrap();
+        }
+
+        escape::href(link.as_bytes(), &mut output);
+        output.write_all(b"\">").unwrap();
+        escape::html(link.as_bytes(), &mut output, false);
+        output.write_all(b"</a>").unwrap();
+
+        last = range.end;
+    }
+
+    escape::html(text[last ..].as_bytes(), &mut output, false);
+    String::from_utf8_lossy(&output).into_owned()
+}
+
+/// A custom pattern added to `Patterns`
+struct Pattern {
+    name: String,
+    matcher: Box<dyn Fn(&str) -> Option<usize>>,
+    url: Box<dyn Fn(&str) -> String>,
+}
+
+/// The text most recently rendered by `normal_text`, and where it was rendered
+struct Run {
+    start: usize,
+    text: Vec<u8>,
+    rendered: Vec<u8>,
+}
+
+/// A match rendered as a link, along with its text as it would otherwise be rendered
+struct Match {
+    link: Vec<u8>,
+    plain: Vec<u8>,
+}
+
+/// Whether the character continues a word, so that a match can't begin or end next to it
+fn is_word(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || c >= 0x80
+}
+
+/// The number of leading characters in the class
+fn count<F>(text: &str, class: F) -> usize
+where F: Fn(u8) -> bool {
+    text.bytes().take_while(|&c| class(c)).count()
+}
+
+/// Renderer wrapper that links text matching custom patterns
+///
+/// Patterns are matched in the text of the document, but not in code, and
+/// matches within the content of links are left as text. Each match is
+/// rendered by the wrapped renderer's `pattern_link`, or as a regular link if
+/// it doesn't implement it.
+pub struct Patterns<R> {
+    base: R,
+    patterns: Vec<Pattern>,
+
+    /// The matches rendered as links since the current block or link began
+    matches: Vec<Match>,
+
+    last: Option<Run>,
+}
+
+impl<R> Patterns<R> where R: Render {
+    /// Wrap the renderer, without any patterns
+    pub fn new(base: R) -> Patterns<R> {
+        Patterns {
+            base: base,
+            patterns: vec![],
+            matches: vec![],
+            last: None,
+        }
+    }
+
+    /// Builder method to add a pattern
+    ///
+    /// The matcher is given the text from each position that begins a word,
+    /// and returns the length of the match at its start, if any. A match must
+    /// also end a word. The url is built from the matched text. Patterns are
+    /// tried in the order they were added.
+    pub fn pattern<M, U>(mut self, name: &str, matcher: M, url: U) -> Patterns<R>
+    where M: Fn(&str) -> Option<usize> + 'static, U: Fn(&str) -> String + 'static {
+        self.patterns.push(Pattern {
+            name: String::from(name),
+            matcher: Box::new(matcher),
+            url: Box::new(url),
+        });
+
+        self
+    }
+
+    /// Builder method to link issue references such as `#1234`
+    ///
+    /// The pattern is named `issue` and links to the number appended to the url.
+    pub fn issues<S>(self, url: S) -> Patterns<R>
+    where S: Into<String> {
+        let url = url.into();
+
+        self.pattern("issue",
+            |text| if text.starts_with('#') {
+                Some(count(&text[1 ..], |c| c.is_ascii_digit())).and_then(|n| if n > 0 { Some(n + 1) } else { None })
+            } else {
+                None
+            },
+            move |text| format!("{}{}", url, &text[1 ..]))
+    }
+
+    /// Builder method to link mentions such as `@user`
+    ///
+    /// User names consist of letters, digits and hyphens, and don't begin with
+    /// a hyphen. The pattern is named `mention` and links to the user name
+    /// appended to the url.
+    pub fn mentions<S>(self, url: S) -> Patterns<R>
+    where S: Into<String> {
+        let url = url.into();
+
+        self.pattern("mention",
+            |text| if text.starts_with('@') && text[1 ..].bytes().next().map_or(false, |c| c.is_ascii_alphanumeric()) {
+                Some(count(&text[1 ..], |c| c.is_ascii_alphanumeric() || c == b'-') + 1)
+            } else {
+                None
+            },
+            move |text| format!("{}{}", url, &text[1 ..]))
+    }
+
+    /// Builder method to link ticket keys of a project, such as `GH-99` for the `GH` project
+    ///
+    /// The pattern is named `key` and links to the key appended to the url.
+    pub fn keys<S>(self, project: &str, url: S) -> Patterns<R>
+    where S: Into<String> {
+        let prefix = format!("{}-", project);
+        let url = url.into();
+
+        self.pattern("key",
+            move |text| if text.starts_with(&prefix[..]) {
+                Some(count(&text[prefix.len() ..], |c| c.is_ascii_digit()))
+                    .and_then(|n| if n > 0 { Some(prefix.len() + n) } else { None })
+            } else {
+                None
+            },
+            move |text| format!("{}{}", url, text))
+    }
+
+    /// Builder method to link commit hashes of 7 to 40 lowercase hex digits
+    ///
+    /// Hashes must contain both a digit and a letter, since otherwise they're
+    /// more likely to be numbers or words such as `defaced`. The pattern is
+    /// named `commit` and links to the hash appended to the url.
+    pub fn commits<S>(self, url: S) -> Patterns<R>
+    where S: Into<String> {
+        let url = url.into();
+
+        self.pattern("commit",
+            |text| {
+                let n = count(text, |c| c.is_ascii_digit() || (b'a' <= c && c <= b'f'));
+                let hash = &text.as_bytes()[.. n];
+
+                if n >= 7 && n <= 40 && hash.iter().any(|c| c.is_ascii_digit()) && hash.iter().any(|c| c.is_ascii_alphabetic()) {
+                    Some(n)
+                } else {
+                    None
+                }
+            },
+            move |text| format!("{}{}", url, text))
+    }
+
+    /// Unwrap the renderer
+    pub fn into_inner(self) -> R {
+        self.base
+    }
+
+    /// The first pattern that matches at the start of the text, and the length of its match
+    fn find(&self, text: &str) -> Option<(usize, usize)> {
+        self.patterns.iter().enumerate()
+            .filter_map(|(index, pattern)| (pattern.matcher)(text).map(|length| (index, length)))
+            .find(|&(_, length)| {
+                length > 0 && length <= text.len() && text.is_char_boundary(length)
+                    && text.as_bytes().get(length).map_or(true, |&c| !is_word(c))
+            })
+    }
+
+    fn render_match(&mut self, ob: &mut Buffer, text: &str, index: usize) {
+        let url = Buffer::from(&(self.patterns[index].url)(text)[..]);
+        let text = Buffer::from(text);
+        let start = ob.len();
+
+        let mut plain = Buffer::new(64);
+        self.base.normal_text(&mut plain, Some(&text));
+
+        if !self.base.pattern_link(ob, &text, &url, &self.patterns[index].name) {
+            if !self.base.link(ob, Some(&plain), Some(&url), None) {
+                ob.pipe(&plain);
+            }
+        }
+
+        // remembered so that the link can be undone within the content of another link
+        if ob.len() > start && ob[start ..] != plain[..] {
+            self.matches.push(Match { link: ob[start ..].to_vec(), plain: plain.to_vec() });
+        }
+    }
+
+    fn render_text(&mut self, ob: &mut Buffer, text: &[u8]) {
+        let text = match str::from_utf8(text) {
+            Ok(text) => text,
+            Err(_) => return self.base.normal_text(ob, Some(&Buffer::from(text))),
+        };
+
+        let bytes = text.as_bytes();
+        let mut last = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let found =
+                if text.is_char_boundary(i) && (i == 0 || !is_word(bytes[i - 1])) {
+                    self.find(&text[i ..])
+                } else {
+                    None
+                };
+
+            match found {
+                Some((index, length)) => {
+                    if last < i {
+                        self.base.normal_text(ob, Some(&Buffer::from(&text[last .. i])));
+                    }
+
+                    self.render_match(ob, &text[i .. i + length], index);
+                    i += length;
+                    last = i;
+                },
+                None => i += 1,
+            }
+        }
+
+        if last < bytes.len() {
+            self.base.normal_text(ob, Some(&Buffer::from(&text[last ..])));
+        }
+    }
+
+    /// Undo the pattern links within the content of a link
+    ///
+    /// The matches in the content are among those rendered since the link's
+    /// block began, and appear in the order they were rendered, so each is
+    /// looked for only after the previous one.
+    fn restore(&mut self, content: Option<&Buffer>) -> Option<Buffer> {
+        let matches = mem::replace(&mut self.matches, vec![]);
+        let content = match content {
+            Some(content) => content,
+            None => return None,
+        };
+
+        let mut restored = Vec::with_capacity(content.len());
+        let mut pos = 0;
+
+        for m in matches {
+            let found = content[pos ..].windows(m.link.len()).position(|w| w == &m.link[..]);
+
+            if let Some(start) = found {
+                restored.extend_from_slice(&content[pos .. pos + start]);
+                restored.extend_from_slice(&m.plain);
+                pos += start + m.link.len();
+            }
+        }
+
+        if pos == 0 {
+            return None;
+        }
+
+        restored.extend_from_slice(&content[pos ..]);
+        Some(Buffer::from(&restored[..]))
+    }
+
+    /// Forget the text and matches of the block that ended
+    fn end_block(&mut self) {
+        self.matches.clear();
+        self.last = None;
+    }
+}
+
+wrap!([R: Render] Patterns<R>);
+
+impl<R> Wrapper for Patterns<R> where R: Render {
+    type Base = R;
+
+    #[inline(always)]
+    fn base(&mut self) -> &mut R {
+        &mut self.base
+    }
+
+    // the blocks whose content is text
+    fn header(&mut self, ob: &mut Buffer, content: Option<&Buffer>, level: i32) {
+        self.end_block();
+        self.base.header(ob, content, level)
+    }
+
+    fn header_with_attributes(&mut self, ob: &mut Buffer, content: Option<&Buffer>, level: i32, attributes: &Attributes) {
+        self.end_block();
+        self.base.header_with_attributes(ob, content, level, attributes)
+    }
+
+    fn list_item(&mut self, ob: &mut Buffer, content: Option<&Buffer>, flags: list::List) {
+        self.end_block();
+        self.base.list_item(ob, content, flags)
+    }
+
+    fn paragraph(&mut self, ob: &mut Buffer, content: Option<&Buffer>) {
+        self.end_block();
+        self.base.paragraph(ob, content)
+    }
+
+    fn table_cell(&mut self, ob: &mut Buffer, content: Option<&Buffer>, flags: Table, column: usize, columns: usize) {
+        self.end_block();
+        self.base.table_cell(ob, content, flags, column, columns)
+    }
+
+    fn definition_term(&mut self, ob: &mut Buffer, content: Option<&Buffer>) {
+        self.end_block();
+        self.base.definition_term(ob, content)
+    }
+
+    fn definition_data(&mut self, ob: &mut Buffer, content: Option<&Buffer>) {
+        self.end_block();
+        self.base.definition_data(ob, content)
+    }
+
+    fn link(&mut self, ob: &mut Buffer, content: Option<&Buffer>, link: Option<&Buffer>, title: Option<&Buffer>) -> bool {
+        let restored = self.restore(content);
+        self.base.link(ob, restored.as_ref().or(content), link, title)
+    }
+
+    fn wiki_link(&mut self, ob: &mut Buffer, target: &Buffer, label: Option<&Buffer>) -> bool {
+        let restored = self.restore(label);
+        self.base.wiki_link(ob, target, restored.as_ref().or(label))
+    }
+
+    fn normal_text(&mut self, ob: &mut Buffer, text: Option<&Buffer>) {
+        let text = match text {
+            Some(text) => text,
+            None => return self.base.normal_text(ob, None),
+        };
+
+        let mut start = ob.len();
+        let mut run = vec![];
+
+        // hoedown splits text at characters that may begin markup, such as `_`
+        // or `@`, so a match may begin in the text rendered by the previous
+        // call if nothing was rendered in between
+        if let Some(last) = self.last.take() {
+            if last.start + last.rendered.len() == ob.len() && ob[last.start ..] == last.rendered[..] {
+                start = last.start;
+                run = last.text;
+                ob.truncate(start);
+            }
+        }
+
+        run.extend_from_slice(text);
+        self.render_text(ob, &run);
+
+        self.last = Some(Run {
+            start: start,
+            rendered: ob[start ..].to_vec(),
+            text: run,
+        });
+    }
+
+    fn before_render(&mut self, output: &mut Buffer, inline_render: bool) {
+        self.end_block();
+        self.base.before_render(output, inline_render)
+    }
+}
@@ -0,0 +1,372 @@
+//! Render markdown from files or stdin with the same options available to the library
+
+extern crate hoedown;
+
+use std::env;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::process;
+
+use hoedown::{Markdown, Render, Buffer, Html, Trace, Extension};
+use hoedown::renderer::html::{self, Flags};
+use hoedown::renderer::page::Page;
+use hoedown::renderer::dynamic::DynRender;
+use hoedown::smartypants::{SmartyPants, Quotes};
+
+const EXTENSIONS: &[(&str, Extension)] = &[
+    ("tables", hoedown::TABLES),
+    ("fenced-code", hoedown::FENCED_CODE),
+    ("footnotes", hoedown::FOOTNOTES),
+    ("autolink", hoedown::AUTOLINK),
+    ("strikethrough", hoedown::STRIKETHROUGH),
+    ("underline", hoedown::UNDERLINE),
+    ("highlight", hoedown::HIGHLIGHT),
+    ("quote", hoedown::QUOTE),
+    ("superscript", hoedown::SUPERSCRIPT),
+    ("math", hoedown::MATH),
+    ("no-intra-emphasis", hoedown::NO_INTRA_EMPHASIS),
+    ("space-headers", hoedown::SPACE_HEADERS),
+    ("math-explicit", hoedown::MATH_EXPLICIT),
+    ("disable-indented-code", hoedown::DISABLE_INDENTED_CODE),
+    ("task-lists", hoedown::TASK_LISTS),
+    ("definition-lists", hoedown::DEFINITION_LISTS),
+    ("admonitions", hoedown::ADMONITIONS),
+    ("wiki-links", hoedown::WIKI_LINKS),
+    ("header-attributes", hoedown::HEADER_ATTRIBUTES),
+];
+
+const FLAGS: &[(&str, Flags)] = &[
+    ("skip-html", html::SKIP_HTML),
+    ("escape", html::ESCAPE),
+    ("hard-wrap", html::HARD_WRAP),
+    ("xhtml", html::USE_XHTML),
+];
+
+#[derive(PartialEq)]
+enum Format {
+    /// The document as html
+    Html,
+
+    /// Only the document's table of contents, as html
+    Toc,
+
+    /// The callbacks that a renderer would need to implement for the document
+    Trace,
+}
+
+struct Options {
+    extensions: Extension,
+    flags: Flags,
+    toc_level: i32,
+    smartypants: bool,
+    inline: bool,
+    max_nesting: usize,
+    format: Format,
+    page: Option<Page>,
+    output: Option<String>,
+    files: Vec<String>,
+}
+
+fn usage() -> String {
+    let mut usage = String::from("\
+Usage: hoedown [OPTION]... [FILE]...
+
+Render markdown to html. With no FILE, or when FILE is -, read standard input.
+
+Options:
+  --toc N             Give headers up to level N ids and precede the output
+                      with a table of contents; 0 disables it (the default)
+  --smartypants       Apply smartypants to the output
+  --inline            Render the input as inline markdown
+  --max-nesting N     Parse blocks up to N levels deep (default 16)
+  --format FORMAT     Output FORMAT: html (the default), toc or trace
+  --source-positions  Add data-sourcepos attributes to top-level blocks
+  --page              Output a complete html page rather than a fragment
+  --title TITLE       Set the page's title, implying --page
+  --stylesheet URL    Link a stylesheet from the page, implying --page
+  --script URL        Include a script in the page, implying --page
+  -o, --output FILE   Write to FILE instead of standard output
+  -h, --help          Show this help
+  -V, --version       Show the version
+
+Extensions:
+");
+
+    for &(name, _) in EXTENSIONS {
+        usage.push_str(&format!("  --{}\n", name));
+    }
+
+    usage.push_str("\nHtml flags:\n");
+
+    for &(name, _) in FLAGS {
+        usage.push_str(&format!("  --{}\n", name));
+    }
+
+    usage
+}
+
+fn parse(args: Vec<String>) -> Result<Options, String> {
+    let mut options = Options {
+        extensions: Extension::empty(),
+        flags: Flags::empty(),
+        toc_level: 0,
+        smartypants: false,
+        inline: false,
+        max_nesting: 16,
+        format: Format::Html,
+        page: None,
+        output: None,
+        files: vec![],
+    };
+
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        if arg == "-" || !arg.starts_with('-') {
+            options.files.push(arg);
+            continue;
+        }
+
+        if arg == "--" {
+            options.files.extend(args.by_ref());
+            break;
+        }
+
+        // options may be given their value as `--name=value` or `--name value`
+        let (name, inline_value) = match arg.find('=') {
+            Some(equals) => (String::from(&arg[.. equals]), Some(String::from(&arg[equals + 1 ..]))),
+            None => (arg.clone(), None),
+        };
+
+        let mut value = || inline_value.clone().or_else(|| args.next())
+            .ok_or(format!("option '{}' requires a value", name));
+
+        let no_value = || match inline_value {
+            Some(_) => Err(format!("option '{}' doesn't take a value", name)),
+            None => Ok(()),
+        };
+
+        match &name[..] {
+            "-h" | "--help" => {
+                try!(no_value());
+                print!("{}", usage());
+                process::exit(0);
+            },
+            "-V" | "--version" => {
+                try!(no_value());
+                let (major, minor, revision) = hoedown::version();
+                println!("hoedown {} (libhoedown {}.{}.{})", env!("CARGO_PKG_VERSION"), major, minor, revision);
+                process::exit(0);
+            },
+            "--toc" => {
+                let level = try!(value());
+                options.toc_level = try!(level.parse().map_err(|_| format!("invalid toc level '{}'", level)));
+            },
+            "--max-nesting" => {
+                let nesting = try!(value());
+                options.max_nesting = try!(nesting.parse().map_err(|_| format!("invalid nesting '{}'", nesting)));
+            },
+            "--format" => {
+                options.format = match &try!(value())[..] {
+                    "html" => Format::Html,
+                    "toc" => Format::Toc,
+                    "trace" => Format::Trace,
+                    format => return Err(format!("unknown format '{}'", format)),
+                };
+            },
+            "--page" => {
+                try!(no_value());
+                options.page = Some(options.page.take().unwrap_or_default());
+            },
+            "--title" => options.page = Some(options.page.take().unwrap_or_default().title(try!(value()))),
+            "--stylesheet" => options.page = Some(options.page.take().unwrap_or_default().stylesheet(try!(value()))),
+            "--script" => options.page = Some(options.page.take().unwrap_or_default().script(try!(value()))),
+            "-o" | "--output" => options.output = Some(try!(value())),
+            "--smartypants" => {
+                try!(no_value());
+                options.smartypants = true;
+            },
+            "--inline" => {
+                try!(no_value());
+                options.inline = true;
+            },
+            "--source-positions" => {
+                try!(no_value());
+                options.extensions.insert(hoedown::SOURCE_POSITIONS);
+                options.flags.insert(html::SOURCE_POSITIONS);
+            },
+            _ => {
+                let extension = EXTENSIONS.iter().find(|&&(n, _)| name == format!("--{}", n));
+                let flag = FLAGS.iter().find(|&&(n, _)| name == format!("--{}", n));
+
+                match (extension, flag) {
+                    (Some(&(_, extension)), _) => {
+                        try!(no_value());
+                        options.extensions.insert(extension);
+                    },
+                    (_, Some(&(_, flag))) => {
+                        try!(no_value());
+                        options.flags.insert(flag);
+                    },
+                    _ => return Err(format!("unknown option '{}'", name)),
+                }
+            },
+        }
+    }
+
+    Ok(options)
+}
+
+/// The html renderer, converting the punctuation of its text if enabled
+fn html_renderer<R>(renderer: R, options: &Options) -> Box<dyn DynRender>
+where R: Render + 'static {
+    if options.smartypants {
+        Box::new(SmartyPants::new(renderer, Quotes::default()))
+    } else {
+        Box::new(renderer)
+    }
+}
+
+fn render(doc: &Markdown, options: &Options) -> Buffer {
+    let mut output = Buffer::new(64);
+
+    match options.format {
+        Format::Html => {
+            let mut html = html_renderer(Html::new(options.flags, options.toc_level), options);
+
+            if options.inline {
+                html.render_inline_to(doc, &mut output);
+            } else if let Some(ref page) = options.page {
+                output = page.clone().toc(options.toc_level).render(&mut html, doc);
+            } else {
+                if options.toc_level > 0 {
+                    html_renderer(Html::toc(options.toc_level), options).render_to(doc, &mut output);
+                }
+
+                html.render_to(doc, &mut output);
+            }
+        },
+        Format::Toc => {
+            // a table of contents is pointless without any levels
+            let level = if options.toc_level > 0 { options.toc_level } else { 6 };
+            html_renderer(Html::toc(level), options).render_to(doc, &mut output);
+        },
+        Format::Trace => {
+            if options.inline {
+                Trace.render_inline_to(doc, &mut output);
+            } else {
+                Trace.render_to(doc, &mut output);
+            }
+        },
+    }
+
+    output
+}
+
+fn run(options: Options) -> io::Result<()> {
+    let mut input = vec![];
+
+    if options.files.is_empty() {
+        try!(io::stdin().read_to_end(&mut input));
+    }
+
+    for (i, file) in options.files.iter().enumerate() {
+        // keep the end of one file from running into the start of the next
+        if i > 0 {
+            input.extend_from_slice(b"\n\n");
+        }
+
+        if file == "-" {
+            try!(io::stdin().read_to_end(&mut input));
+        } else {
+            try!(try!(File::open(file)).read_to_end(&mut input));
+        }
+    }
+
+    let doc =
+        Markdown::from(&input[..])
+        .extensions(options.extensions)
+        .max_nesting(options.max_nesting);
+
+    let output = render(&doc, &options);
+
+    match options.output {
+        Some(ref path) => try!(File::create(path)).write_all(&output),
+        None => io::stdout().write_all(&output),
+    }
+}
+
+fn main() {
+    let options = match parse(env::args().skip(1).collect()) {
+        Ok(options) => options,
+        Err(error) => {
+            let _ = writeln!(io::stderr(), "hoedown: {}\nTry 'hoedown --help' for more information.", error);
+            process::exit(2);
+        },
+    };
+
+    if let Err(error) = run(options) {
+        let _ = writeln!(io::stderr(), "hoedown: {}", error);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, Format};
+    use hoedown::renderer::html;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|&arg| String::from(arg)).collect()
+    }
+
+    #[test]
+    fn test_flags() {
+        let options = parse(args(&["--tables", "--footnotes", "--hard-wrap", "--source-positions", "--inline"])).unwrap();
+
+        assert_eq!(options.extensions, hoedown::TABLES | hoedown::FOOTNOTES | hoedown::SOURCE_POSITIONS);
+        assert_eq!(options.flags, html::HARD_WRAP | html::SOURCE_POSITIONS);
+        assert!(options.inline);
+        assert!(!options.smartypants);
+        assert!(options.files.is_empty());
+    }
+
+    #[test]
+    fn test_values() {
+        let options = parse(args(&["--format=trace", "--max-nesting", "4", "-o", "out.html", "--title=Notes"])).unwrap();
+
+        assert!(options.format == Format::Trace);
+        assert_eq!(options.max_nesting, 4);
+        assert_eq!(options.output, Some(String::from("out.html")));
+        assert!(options.page.is_some());
+
+        assert_eq!(parse(args(&["--format", "pdf"])).err(), Some(String::from("unknown format 'pdf'")));
+        assert_eq!(parse(args(&["--output"])).err(), Some(String::from("option '--output' requires a value")));
+    }
+
+    #[test]
+    fn test_files() {
+        let options = parse(args(&["a.md", "-", "--tables", "--", "--not-an-option", "-"])).unwrap();
+
+        assert_eq!(options.files, args(&["a.md", "-", "--not-an-option", "-"]));
+        assert_eq!(options.extensions, hoedown::TABLES);
+    }
+
+    #[test]
+    fn test_unknown_option() {
+        assert_eq!(parse(args(&["--tables", "--bogus"])).err(), Some(String::from("unknown option '--bogus'")));
+        assert_eq!(parse(args(&["-x"])).err(), Some(String::from("unknown option '-x'")));
+        assert_eq!(parse(args(&["--bogus=1"])).err(), Some(String::from("unknown option '--bogus'")));
+    }
+
+    #[test]
+    fn test_toc_level() {
+        assert_eq!(parse(args(&[])).unwrap().toc_level, 0);
+        assert_eq!(parse(args(&["--toc", "3"])).unwrap().toc_level, 3);
+        assert_eq!(parse(args(&["--toc=2"])).unwrap().toc_level, 2);
+
+        assert_eq!(parse(args(&["--toc", "deep"])).err(), Some(String::from("invalid toc level 'deep'")));
+        assert_eq!(parse(args(&["--toc="])).err(), Some(String::from("invalid toc level ''")));
+        assert_eq!(parse(args(&["--toc"])).err(), Some(String::from("option '--toc' requires a value")));
+    }
+}
@@ -84,6 +84,17 @@ impl Buffer {
         unsafe { (*self.buffer).size }
     }
 
+    /// Shorten the buffer to the given length
+    ///
+    /// Has no effect if the buffer is already shorter than that.
+    pub fn truncate(&mut self, len: usize) {
+        unsafe {
+            if len < (*self.buffer).size {
+                (*self.buffer).size = len as size_t;
+            }
+        }
+    }
+
     /// Get a raw constant pointer to the buffer data
     pub fn data_ptr(&self) -> *const u8 {
         unsafe { (*self.buffer).data }
@@ -145,7 +156,7 @@ impl<'a> From<&'a [u8]> for Buffer {
     /// Create a buffer from bytes
     fn from(s: &[u8]) -> Buffer {
         let mut buffer = Buffer::new(64);
-        buffer.write(s).unwrap();
+        buffer.write_all(s).unwrap();
         buffer
     }
 }
@@ -181,6 +192,11 @@ impl AsRef<[u8]> for Buffer {
             let data = (*self.buffer).data;
             let size = (*self.buffer).size as usize;
 
+            // hoedown doesn't allocate until something is written
+            if data.is_null() {
+                return &[];
+            }
+
             mem::transmute(slice::from_raw_parts(data, size))
         }
     }
@@ -193,6 +209,10 @@ impl AsMut<[u8]> for Buffer {
             let data = (*self.buffer).data;
             let size = (*self.buffer).size as usize;
 
+            if data.is_null() {
+                return &mut [];
+            }
+
             slice::from_raw_parts_mut(data, size)
         }
     }
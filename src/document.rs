@@ -2,6 +2,7 @@ use libc::size_t;
 
 use extensions::Extension;
 use buffer::Buffer;
+use extended;
 use ffi::{
     hoedown_document,
     hoedown_renderer,
@@ -20,12 +21,15 @@ impl Document {
     /// Construct a new document with the given renderer, extensions, and maximum nesting
     ///
     /// `max_nesting` refers to the maximum block depth that should be parsed
+    ///
+    /// Extensions that are implemented by these bindings are not passed on to hoedown.
     pub fn new(
         renderer: &hoedown_renderer,
         extensions: Extension,
         max_nesting: usize
     ) -> Document {
         let doc = unsafe {
+            let extensions = extensions - extended::extensions();
             hoedown_document_new(renderer, extensions.bits(), max_nesting as size_t)
         };
 
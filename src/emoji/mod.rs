@@ -0,0 +1,301 @@
+//! Emoji shortcode expansion
+//!
+//! Replaces shortcodes such as `:smile:` in the document's text with the
+//! emoji they refer to, either as the unicode emoji itself or as an image.
+//! Code spans and code blocks are left untouched.
+//!
+//! This is available as the `Emoji` wrapper around any renderer, or as an
+//! option on the `Html` renderer.
+//!
+//!``` rust
+//!# use hoedown::{Markdown, Render, Html};
+//!# use hoedown::renderer::html::Flags;
+//!# use hoedown::emoji::{Emoji, Style};
+//!let doc = Markdown::new("ship it :rocket: but not `:rocket:`");
+//!let mut html = Emoji::new(Html::new(Flags::empty(), 0), Style::Unicode);
+//!
+//!assert_eq!(
+//!    html.render(&doc).to_str().unwrap(),
+//!    "<p>ship it \u{1F680} but not <code>:rocket:</code></p>\n");
+//!```
+
+use std::io::Write;
+use std::mem;
+
+use buffer::Buffer;
+use escape;
+use renderer::{Render, Attributes, SourcePosition, AutoLink, Table, list};
+use renderer::wrapper::Wrapper;
+
+mod table;
+
+/// Find the emoji for a shortcode, given without its colons
+///
+///``` rust
+///# use hoedown::emoji;
+///assert_eq!(emoji::lookup("tada"), Some("\u{1F389}"));
+///assert_eq!(emoji::lookup("not_an_emoji"), None);
+///```
+pub fn lookup(shortcode: &str) -> Option<&'static str> {
+    table::EMOJI.binary_search_by(|&(name, _)| name.cmp(shortcode))
+        .ok()
+        .map(|i| table::EMOJI[i].1)
+}
+
+/// How emoji are rendered
+#[derive(Clone, Debug)]
+pub enum Style {
+    /// Render the unicode emoji as text
+    Unicode,
+
+    /// Render an `<img>` tag whose url is given by the template
+    ///
+    /// In the template, `{name}` is replaced by the shortcode and `{codepoint}`
+    /// by the emoji's code points in lowercase hexadecimal, separated by dashes,
+    /// e.g. `https://example.com/emoji/{codepoint}.png`.
+    Image(String),
+}
+
+/// Whether the character can be part of a shortcode
+fn is_shortcode(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || c == b'+' || c == b'-'
+}
+
+fn is_whitespace(c: u8) -> bool {
+    c == b' ' || c == b'\t' || c == b'\n' || c == b'\r'
+}
+
+/// Replaces shortcodes in the text given to `normal_text`
+///
+/// Hoedown splits runs of text at characters that may begin markup, such as
+/// the `_` in `:heart_eyes:`. Consecutive calls to `normal_text` are therefore
+/// joined back together: the output of any text that may be the start of a
+/// shortcode is removed and rendered again along with the text that follows
+/// it. Any other callback ends the run of text, and must call `reset`.
+struct Replacer {
+    style: Style,
+
+    /// The offset in the output buffer at which the pending text's output begins
+    start: usize,
+
+    /// Trailing text that may be the start of a shortcode
+    pending: Vec<u8>,
+
+    /// The output of the pending text
+    output: Vec<u8>,
+}
+
+impl Replacer {
+    pub fn new(style: Style) -> Replacer {
+        Replacer {
+            style: style,
+            start: 0,
+            pending: vec![],
+            output: vec![],
+        }
+    }
+
+    /// Forget about any pending text, at the end of a run of text
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.output.clear();
+    }
+
+    /// Whether the text being rendered into the buffer continues the pending text
+    ///
+    /// Every other callback ends the run of text, so the pending text can only
+    /// be followed by text in another buffer at the start of a span's content,
+    /// which is rendered into an empty buffer before the span's callback.
+    fn continues(&self, ob: &Buffer) -> bool {
+        !self.output.is_empty() && ob.len() == self.start + self.output.len()
+    }
+
+    /// Render the text, replacing its shortcodes, with `render` used for the
+    /// text between them and for unicode emoji
+    pub fn normal_text<F>(&mut self, ob: &mut Buffer, text: &[u8], mut render: F)
+    where F: FnMut(&mut Buffer, &[u8]) {
+        let mut text = text.to_vec();
+
+        if self.continues(ob) {
+            ob.truncate(self.start);
+            text = mem::replace(&mut self.pending, vec![]).into_iter().chain(text).collect();
+        }
+
+        // shortcodes can't span whitespace, so only the text after it is kept pending
+        let split = text.iter().rposition(|&c| is_whitespace(c)).map_or(0, |i| i + 1);
+
+        self.replace(ob, &text[.. split], &mut render);
+        self.start = ob.len();
+        self.replace(ob, &text[split ..], &mut render);
+
+        self.output = ob[self.start ..].to_vec();
+        self.pending = text[split ..].to_vec();
+    }
+
+    fn replace<F>(&self, ob: &mut Buffer, text: &[u8], render: &mut F)
+    where F: FnMut(&mut Buffer, &[u8]) {
+        let mut plain = 0;
+        let mut pos = 0;
+
+        while let Some(open) = text[pos ..].iter().position(|&c| c == b':').map(|i| pos + i) {
+            let close = match text[open + 1 ..].iter().position(|&c| !is_shortcode(c)).map(|i| open + 1 + i) {
+                Some(close) if text[close] == b':' => close,
+                Some(close) => { pos = close; continue },
+                None => break,
+            };
+
+            let name = &text[open + 1 .. close];
+            let emoji = ::std::str::from_utf8(name).ok().and_then(lookup);
+
+            match emoji {
+                Some(emoji) => {
+                    if open > plain {
+                        render(ob, &text[plain .. open]);
+                    }
+
+                    self.emoji(ob, name, emoji, render);
+                    plain = close + 1;
+                    pos = close + 1;
+                },
+                None => pos = close,
+            }
+        }
+
+        if text.len() > plain {
+            render(ob, &text[plain ..]);
+        }
+    }
+
+    fn emoji<F>(&self, ob: &mut Buffer, name: &[u8], emoji: &str, render: &mut F)
+    where F: FnMut(&mut Buffer, &[u8]) {
+        match self.style {
+            Style::Unicode => render(ob, emoji.as_bytes()),
+            Style::Image(ref template) => {
+                let name = String::from_utf8_lossy(name);
+                let codepoint =
+                    emoji.chars()
+                    .filter(|&c| c != '\u{FE0F}')
+                    .map(|c| format!("{:x}", c as u32))
+                    .collect::<Vec<_>>()
+                    .join("-");
+
+                let url = template.replace("{name}", &name).replace("{codepoint}", &codepoint);
+
+                write!(ob, "<img class=\"emoji\" title=\":{}:\" alt=\"{}\" src=\"", name, emoji).unwrap();
+                escape::href(url.as_bytes(), ob);
+                ob.write_all(b"\">").unwrap();
+            },
+        }
+    }
+}
+
+/// Renderer wrapper that replaces emoji shortcodes in the text of another renderer
+///
+/// The text between shortcodes, and unicode emoji, are rendered by the
+/// wrapped renderer's `normal_text`.
+pub struct Emoji<R> {
+    base: R,
+    replacer: Replacer,
+}
+
+impl<R> Emoji<R> where R: Render {
+    /// Wrap the renderer, rendering emoji in the given style
+    pub fn new(base: R, style: Style) -> Emoji<R> {
+        Emoji {
+            base: base,
+            replacer: Replacer::new(style),
+        }
+    }
+
+    /// Unwrap the renderer
+    pub fn into_inner(self) -> R {
+        self.base
+    }
+}
+
+wrap!([R: Render] Emoji<R>);
+
+/// Implement `Wrapper` for `Emoji`, ending the run of text in every callback
+/// other than those given
+macro_rules! end_text {
+    ($(fn $name:ident($($arg:ident: $ty:ty),*) $(-> $ret:ty)*;)+ { $($rest:tt)* }) => {
+        impl<R> Wrapper for Emoji<R> where R: Render {
+            type Base = R;
+
+            #[inline(always)]
+            fn base(&mut self) -> &mut R {
+                &mut self.base
+            }
+
+            $(
+                fn $name(&mut self, ob: &mut Buffer, $($arg: $ty),*) $(-> $ret)* {
+                    self.replacer.reset();
+                    self.base.$name(ob, $($arg),*)
+                }
+            )+
+
+            $($rest)*
+        }
+    }
+}
+
+end_text! {
+    fn code_block(code: Option<&Buffer>, lang: Option<&Buffer>);
+    fn quote_block(content: Option<&Buffer>);
+    fn header(content: Option<&Buffer>, level: i32);
+    fn header_with_attributes(content: Option<&Buffer>, level: i32, attributes: &Attributes);
+    fn horizontal_rule();
+    fn list(content: Option<&Buffer>, flags: list::List);
+    fn list_item(content: Option<&Buffer>, flags: list::List);
+    fn paragraph(content: Option<&Buffer>);
+    fn table(content: Option<&Buffer>);
+    fn table_header(content: Option<&Buffer>);
+    fn table_body(content: Option<&Buffer>);
+    fn table_row(content: Option<&Buffer>, columns: usize);
+    fn table_cell(content: Option<&Buffer>, flags: Table, column: usize, columns: usize);
+    fn footnotes(content: Option<&Buffer>);
+    fn footnote_definition(content: Option<&Buffer>, num: u32);
+    fn html_block(text: Option<&Buffer>);
+    fn positioned_block(content: Option<&Buffer>, position: &SourcePosition);
+    fn definition_list(content: Option<&Buffer>);
+    fn definition_term(content: Option<&Buffer>);
+    fn definition_data(content: Option<&Buffer>);
+    fn admonition(content: Option<&Buffer>, kind: &Buffer, title: Option<&Buffer>);
+
+    fn autolink(link: Option<&Buffer>, link_type: AutoLink) -> bool;
+    fn code_span(text: Option<&Buffer>) -> bool;
+    fn double_emphasis(content: Option<&Buffer>) -> bool;
+    fn emphasis(content: Option<&Buffer>) -> bool;
+    fn underline(content: Option<&Buffer>) -> bool;
+    fn highlight(content: Option<&Buffer>) -> bool;
+    fn quote_span(content: Option<&Buffer>) -> bool;
+    fn image(link: Option<&Buffer>, title: Option<&Buffer>, alt: Option<&Buffer>) -> bool;
+    fn line_break() -> bool;
+    fn link(content: Option<&Buffer>, link: Option<&Buffer>, title: Option<&Buffer>) -> bool;
+    fn wiki_link(target: &Buffer, label: Option<&Buffer>) -> bool;
+    fn pattern_link(text: &Buffer, link: &Buffer, name: &str) -> bool;
+    fn triple_emphasis(content: Option<&Buffer>) -> bool;
+    fn strikethrough(content: Option<&Buffer>) -> bool;
+    fn superscript(content: Option<&Buffer>) -> bool;
+    fn footnote_reference(num: u32) -> bool;
+    fn math(text: Option<&Buffer>, displaymode: i32) -> bool;
+    fn html_span(text: Option<&Buffer>) -> bool;
+
+    fn entity(text: Option<&Buffer>);
+    fn after_render(inline_render: bool);
+
+    {
+        fn normal_text(&mut self, ob: &mut Buffer, text: Option<&Buffer>) {
+            let Emoji { ref mut base, ref mut replacer } = *self;
+
+            if let Some(text) = text {
+                replacer.normal_text(ob, text, |ob, text| base.normal_text(ob, Some(&Buffer::from(text))));
+            }
+        }
+
+        fn before_render(&mut self, output: &mut Buffer, inline_render: bool) {
+            self.replacer.reset();
+            self.base.before_render(output, inline_render)
+        }
+    }
+}
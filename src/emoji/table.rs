@@ -0,0 +1,301 @@
+//! Emoji shortcode table
+
+/// Shortcodes and their emoji, sorted by shortcode
+pub static EMOJI: &[(&str, &str)] = &[
+    ("+1", "\u{1F44D}"),
+    ("-1", "\u{1F44E}"),
+    ("100", "\u{1F4AF}"),
+    ("1st_place_medal", "\u{1F947}"),
+    ("2nd_place_medal", "\u{1F948}"),
+    ("3rd_place_medal", "\u{1F949}"),
+    ("airplane", "\u{2708}\u{FE0F}"),
+    ("alarm_clock", "\u{23F0}"),
+    ("alien", "\u{1F47D}"),
+    ("anchor", "\u{2693}"),
+    ("angry", "\u{1F620}"),
+    ("apple", "\u{1F34E}"),
+    ("arrow_down", "\u{2B07}\u{FE0F}"),
+    ("arrow_left", "\u{2B05}\u{FE0F}"),
+    ("arrow_right", "\u{27A1}\u{FE0F}"),
+    ("arrow_up", "\u{2B06}\u{FE0F}"),
+    ("arrows_counterclockwise", "\u{1F504}"),
+    ("art", "\u{1F3A8}"),
+    ("astonished", "\u{1F632}"),
+    ("baby", "\u{1F476}"),
+    ("back", "\u{1F519}"),
+    ("balloon", "\u{1F388}"),
+    ("bar_chart", "\u{1F4CA}"),
+    ("basketball", "\u{1F3C0}"),
+    ("battery", "\u{1F50B}"),
+    ("beer", "\u{1F37A}"),
+    ("beers", "\u{1F37B}"),
+    ("bell", "\u{1F514}"),
+    ("bike", "\u{1F6B2}"),
+    ("bird", "\u{1F426}"),
+    ("black_circle", "\u{26AB}"),
+    ("blue_heart", "\u{1F499}"),
+    ("blush", "\u{1F60A}"),
+    ("bomb", "\u{1F4A3}"),
+    ("book", "\u{1F4D6}"),
+    ("bookmark", "\u{1F516}"),
+    ("books", "\u{1F4DA}"),
+    ("boom", "\u{1F4A5}"),
+    ("broken_heart", "\u{1F494}"),
+    ("bug", "\u{1F41B}"),
+    ("bulb", "\u{1F4A1}"),
+    ("bus", "\u{1F68C}"),
+    ("cactus", "\u{1F335}"),
+    ("cake", "\u{1F370}"),
+    ("calendar", "\u{1F4C6}"),
+    ("call_me_hand", "\u{1F919}"),
+    ("camera", "\u{1F4F7}"),
+    ("car", "\u{1F697}"),
+    ("cat", "\u{1F431}"),
+    ("chart_with_downwards_trend", "\u{1F4C9}"),
+    ("chart_with_upwards_trend", "\u{1F4C8}"),
+    ("checkered_flag", "\u{1F3C1}"),
+    ("christmas_tree", "\u{1F384}"),
+    ("clap", "\u{1F44F}"),
+    ("clipboard", "\u{1F4CB}"),
+    ("cloud", "\u{2601}\u{FE0F}"),
+    ("coffee", "\u{2615}"),
+    ("computer", "\u{1F4BB}"),
+    ("confused", "\u{1F615}"),
+    ("construction", "\u{1F6A7}"),
+    ("cookie", "\u{1F36A}"),
+    ("cool", "\u{1F192}"),
+    ("copyright", "\u{A9}\u{FE0F}"),
+    ("crescent_moon", "\u{1F319}"),
+    ("crossed_fingers", "\u{1F91E}"),
+    ("crown", "\u{1F451}"),
+    ("cry", "\u{1F622}"),
+    ("crying_cat_face", "\u{1F63F}"),
+    ("cyclone", "\u{1F300}"),
+    ("dancer", "\u{1F483}"),
+    ("dart", "\u{1F3AF}"),
+    ("disappointed", "\u{1F61E}"),
+    ("dizzy", "\u{1F4AB}"),
+    ("dog", "\u{1F436}"),
+    ("droplet", "\u{1F4A7}"),
+    ("earth_americas", "\u{1F30E}"),
+    ("eight_spoked_asterisk", "\u{2733}\u{FE0F}"),
+    ("electric_plug", "\u{1F50C}"),
+    ("email", "\u{1F4E7}"),
+    ("end", "\u{1F51A}"),
+    ("envelope", "\u{2709}\u{FE0F}"),
+    ("evergreen_tree", "\u{1F332}"),
+    ("exclamation", "\u{2757}"),
+    ("eyes", "\u{1F440}"),
+    ("face_with_head_bandage", "\u{1F915}"),
+    ("facepalm", "\u{1F926}"),
+    ("fast_forward", "\u{23E9}"),
+    ("file_folder", "\u{1F4C1}"),
+    ("fire", "\u{1F525}"),
+    ("fish", "\u{1F41F}"),
+    ("fist", "\u{270A}"),
+    ("flushed", "\u{1F633}"),
+    ("football", "\u{1F3C8}"),
+    ("four_leaf_clover", "\u{1F340}"),
+    ("free", "\u{1F193}"),
+    ("full_moon", "\u{1F315}"),
+    ("game_die", "\u{1F3B2}"),
+    ("gear", "\u{2699}\u{FE0F}"),
+    ("gem", "\u{1F48E}"),
+    ("ghost", "\u{1F47B}"),
+    ("gift", "\u{1F381}"),
+    ("globe_with_meridians", "\u{1F310}"),
+    ("green_heart", "\u{1F49A}"),
+    ("grin", "\u{1F601}"),
+    ("grinning", "\u{1F600}"),
+    ("guitar", "\u{1F3B8}"),
+    ("hamburger", "\u{1F354}"),
+    ("hammer", "\u{1F528}"),
+    ("handshake", "\u{1F91D}"),
+    ("hankey", "\u{1F4A9}"),
+    ("headphones", "\u{1F3A7}"),
+    ("heart", "\u{2764}\u{FE0F}"),
+    ("heart_eyes", "\u{1F60D}"),
+    ("heavy_check_mark", "\u{2714}\u{FE0F}"),
+    ("heavy_division_sign", "\u{2797}"),
+    ("heavy_exclamation_mark", "\u{2757}"),
+    ("heavy_minus_sign", "\u{2796}"),
+    ("heavy_multiplication_x", "\u{2716}\u{FE0F}"),
+    ("heavy_plus_sign", "\u{2795}"),
+    ("hocho", "\u{1F52A}"),
+    ("hotsprings", "\u{2668}\u{FE0F}"),
+    ("hourglass", "\u{231B}"),
+    ("hourglass_flowing_sand", "\u{23F3}"),
+    ("house", "\u{1F3E0}"),
+    ("hugs", "\u{1F917}"),
+    ("inbox_tray", "\u{1F4E5}"),
+    ("infinity", "\u{267E}\u{FE0F}"),
+    ("information_source", "\u{2139}\u{FE0F}"),
+    ("innocent", "\u{1F607}"),
+    ("iphone", "\u{1F4F1}"),
+    ("jack_o_lantern", "\u{1F383}"),
+    ("joy", "\u{1F602}"),
+    ("key", "\u{1F511}"),
+    ("kiss", "\u{1F48B}"),
+    ("kissing", "\u{1F617}"),
+    ("label", "\u{1F3F7}\u{FE0F}"),
+    ("large_blue_circle", "\u{1F535}"),
+    ("laughing", "\u{1F606}"),
+    ("link", "\u{1F517}"),
+    ("lipstick", "\u{1F484}"),
+    ("lock", "\u{1F512}"),
+    ("lock_with_ink_pen", "\u{1F50F}"),
+    ("loudspeaker", "\u{1F4E2}"),
+    ("mag", "\u{1F50D}"),
+    ("mega", "\u{1F4E3}"),
+    ("memo", "\u{1F4DD}"),
+    ("metal", "\u{1F918}"),
+    ("microscope", "\u{1F52C}"),
+    ("money_with_wings", "\u{1F4B8}"),
+    ("moneybag", "\u{1F4B0}"),
+    ("monkey", "\u{1F412}"),
+    ("moon", "\u{1F314}"),
+    ("muscle", "\u{1F4AA}"),
+    ("musical_note", "\u{1F3B5}"),
+    ("mute", "\u{1F507}"),
+    ("nail_care", "\u{1F485}"),
+    ("nerd_face", "\u{1F913}"),
+    ("neutral_face", "\u{1F610}"),
+    ("new", "\u{1F195}"),
+    ("new_moon", "\u{1F311}"),
+    ("newspaper", "\u{1F4F0}"),
+    ("no_entry", "\u{26D4}"),
+    ("no_entry_sign", "\u{1F6AB}"),
+    ("notebook", "\u{1F4D3}"),
+    ("notes", "\u{1F3B6}"),
+    ("nut_and_bolt", "\u{1F529}"),
+    ("ocean", "\u{1F30A}"),
+    ("ok", "\u{1F197}"),
+    ("ok_hand", "\u{1F44C}"),
+    ("on", "\u{1F51B}"),
+    ("open_file_folder", "\u{1F4C2}"),
+    ("open_mouth", "\u{1F62E}"),
+    ("outbox_tray", "\u{1F4E4}"),
+    ("package", "\u{1F4E6}"),
+    ("page_facing_up", "\u{1F4C4}"),
+    ("page_with_curl", "\u{1F4C3}"),
+    ("panda_face", "\u{1F43C}"),
+    ("paperclip", "\u{1F4CE}"),
+    ("pencil", "\u{1F4DD}"),
+    ("pencil2", "\u{270F}\u{FE0F}"),
+    ("penguin", "\u{1F427}"),
+    ("pensive", "\u{1F614}"),
+    ("phone", "\u{260E}\u{FE0F}"),
+    ("pill", "\u{1F48A}"),
+    ("pizza", "\u{1F355}"),
+    ("point_down", "\u{1F447}"),
+    ("point_left", "\u{1F448}"),
+    ("point_right", "\u{1F449}"),
+    ("point_up", "\u{261D}\u{FE0F}"),
+    ("poop", "\u{1F4A9}"),
+    ("pray", "\u{1F64F}"),
+    ("punch", "\u{1F44A}"),
+    ("purple_heart", "\u{1F49C}"),
+    ("pushpin", "\u{1F4CC}"),
+    ("question", "\u{2753}"),
+    ("radio", "\u{1F4FB}"),
+    ("rage", "\u{1F621}"),
+    ("rainbow", "\u{1F308}"),
+    ("raised_hands", "\u{1F64C}"),
+    ("raising_hand", "\u{1F64B}"),
+    ("recycle", "\u{267B}\u{FE0F}"),
+    ("red_circle", "\u{1F534}"),
+    ("registered", "\u{AE}\u{FE0F}"),
+    ("relaxed", "\u{263A}\u{FE0F}"),
+    ("relieved", "\u{1F60C}"),
+    ("repeat", "\u{1F501}"),
+    ("rewind", "\u{23EA}"),
+    ("robot", "\u{1F916}"),
+    ("rocket", "\u{1F680}"),
+    ("rofl", "\u{1F923}"),
+    ("rose", "\u{1F339}"),
+    ("running", "\u{1F3C3}"),
+    ("santa", "\u{1F385}"),
+    ("scissors", "\u{2702}\u{FE0F}"),
+    ("scream", "\u{1F631}"),
+    ("scroll", "\u{1F4DC}"),
+    ("see_no_evil", "\u{1F648}"),
+    ("seedling", "\u{1F331}"),
+    ("selfie", "\u{1F933}"),
+    ("ship", "\u{1F6A2}"),
+    ("shit", "\u{1F4A9}"),
+    ("shrug", "\u{1F937}"),
+    ("skull", "\u{1F480}"),
+    ("skull_and_crossbones", "\u{2620}\u{FE0F}"),
+    ("sleeping", "\u{1F634}"),
+    ("slightly_smiling_face", "\u{1F642}"),
+    ("smile", "\u{1F604}"),
+    ("smiley", "\u{1F603}"),
+    ("smirk", "\u{1F60F}"),
+    ("snake", "\u{1F40D}"),
+    ("snowflake", "\u{2744}\u{FE0F}"),
+    ("snowman", "\u{26C4}"),
+    ("sob", "\u{1F62D}"),
+    ("soccer", "\u{26BD}"),
+    ("soon", "\u{1F51C}"),
+    ("sos", "\u{1F198}"),
+    ("space_invader", "\u{1F47E}"),
+    ("sparkle", "\u{2747}\u{FE0F}"),
+    ("sparkles", "\u{2728}"),
+    ("speaker", "\u{1F508}"),
+    ("speech_balloon", "\u{1F4AC}"),
+    ("star", "\u{2B50}"),
+    ("star2", "\u{1F31F}"),
+    ("stopwatch", "\u{23F1}\u{FE0F}"),
+    ("stuck_out_tongue", "\u{1F61B}"),
+    ("sun_with_face", "\u{1F31E}"),
+    ("sunflower", "\u{1F33B}"),
+    ("sunglasses", "\u{1F60E}"),
+    ("sunny", "\u{2600}\u{FE0F}"),
+    ("sweat", "\u{1F613}"),
+    ("sweat_drops", "\u{1F4A6}"),
+    ("sweat_smile", "\u{1F605}"),
+    ("syringe", "\u{1F489}"),
+    ("tada", "\u{1F389}"),
+    ("taxi", "\u{1F695}"),
+    ("tea", "\u{1F375}"),
+    ("telescope", "\u{1F52D}"),
+    ("thinking", "\u{1F914}"),
+    ("thought_balloon", "\u{1F4AD}"),
+    ("thumbsdown", "\u{1F44E}"),
+    ("thumbsup", "\u{1F44D}"),
+    ("tired_face", "\u{1F62B}"),
+    ("tm", "\u{2122}\u{FE0F}"),
+    ("top", "\u{1F51D}"),
+    ("train", "\u{1F68B}"),
+    ("triangular_flag_on_post", "\u{1F6A9}"),
+    ("trophy", "\u{1F3C6}"),
+    ("trumpet", "\u{1F3BA}"),
+    ("tulip", "\u{1F337}"),
+    ("turtle", "\u{1F422}"),
+    ("tv", "\u{1F4FA}"),
+    ("umbrella", "\u{2614}"),
+    ("unamused", "\u{1F612}"),
+    ("unlock", "\u{1F513}"),
+    ("up", "\u{1F199}"),
+    ("upside_down_face", "\u{1F643}"),
+    ("v", "\u{270C}\u{FE0F}"),
+    ("video_game", "\u{1F3AE}"),
+    ("volcano", "\u{1F30B}"),
+    ("walking", "\u{1F6B6}"),
+    ("warning", "\u{26A0}\u{FE0F}"),
+    ("watch", "\u{231A}"),
+    ("wave", "\u{1F44B}"),
+    ("whale", "\u{1F433}"),
+    ("white_check_mark", "\u{2705}"),
+    ("white_circle", "\u{26AA}"),
+    ("wine_glass", "\u{1F377}"),
+    ("wink", "\u{1F609}"),
+    ("worried", "\u{1F61F}"),
+    ("wrench", "\u{1F527}"),
+    ("writing_hand", "\u{270D}\u{FE0F}"),
+    ("x", "\u{274C}"),
+    ("yellow_heart", "\u{1F49B}"),
+    ("yum", "\u{1F60B}"),
+    ("zap", "\u{26A1}"),
+    ("zzz", "\u{1F4A4}"),
+];
@@ -0,0 +1,44 @@
+//! Html and url escaping
+//!
+//! Safe bindings to hoedown's escaping functions, for renderers that write
+//! html themselves.
+//!
+//!``` rust
+//!# use hoedown::escape;
+//!assert_eq!(escape::html_to_string("<b>Tom & Jerry</b>", false), "&lt;b&gt;Tom &amp; Jerry&lt;/b&gt;");
+//!assert_eq!(escape::href_to_string("/search?q=\"rust\"&page=2"), "/search?q=%22rust%22&amp;page=2");
+//!```
+
+use libc::c_int;
+
+use buffer::Buffer;
+use ffi::{hoedown_escape_html, hoedown_escape_href};
+
+/// Escape text for use in html content or attribute values, writing it to the output
+///
+/// In secure mode, `/` is escaped as well.
+pub fn html(text: &[u8], output: &mut Buffer, secure: bool) {
+    unsafe { hoedown_escape_html(output.as_mut(), text.as_ptr(), text.len(), secure as c_int); }
+}
+
+/// Escape a url for use in an `href` or `src` attribute, writing it to the output
+///
+/// Characters that aren't allowed in urls are percent-encoded, and `&` and `'`
+/// are escaped as html entities.
+pub fn href(text: &[u8], output: &mut Buffer) {
+    unsafe { hoedown_escape_href(output.as_mut(), text.as_ptr(), text.len()); }
+}
+
+/// Escape text for use in html, returning it as a string
+pub fn html_to_string(text: &str, secure: bool) -> String {
+    let mut output = Buffer::new(text.len() + 16);
+    html(text.as_bytes(), &mut output, secure);
+    String::from_utf8_lossy(&output).into_owned()
+}
+
+/// Escape a url for use in an attribute, returning it as a string
+pub fn href_to_string(text: &str) -> String {
+    let mut output = Buffer::new(text.len() + 16);
+    href(text.as_bytes(), &mut output);
+    String::from_utf8_lossy(&output).into_owned()
+}
@@ -0,0 +1,158 @@
+//! Admonition parsing
+//!
+//! Both the MkDocs syntax, where the body is indented beneath a `!!!` line:
+//!
+//!```text
+//!!!! warning "Optional title"
+//!    The body of the admonition.
+//!```
+//!
+//! and the GitHub syntax, where a blockquote begins with the kind:
+//!
+//!```text
+//!> [!WARNING]
+//!> The body of the admonition.
+//!```
+
+use super::source::{is_blank, indentation, unindent, trim_end};
+
+/// An admonition, e.g. a note or a warning
+pub struct Admonition {
+    /// The kind of admonition, in lowercase
+    pub kind: Vec<u8>,
+
+    /// The title, if one was given
+    pub title: Option<Vec<u8>>,
+
+    /// The markdown of the body
+    pub text: Vec<u8>,
+}
+
+fn is_kind(kind: &[u8]) -> bool {
+    !kind.is_empty() && kind.iter().all(|&c| c.is_ascii_alphanumeric() || c == b'-' || c == b'_')
+}
+
+/// Parse an admonition at the start of the given lines
+///
+/// Returns the admonition along with the number of lines it spans,
+/// excluding any trailing blank lines.
+pub fn parse(lines: &[&[u8]]) -> Option<(Admonition, usize)> {
+    match lines.first() {
+        Some(line) if indentation(line) <= 3 => {
+            let line = unindent(line, 3);
+
+            if line.starts_with(b"!!!") {
+                indented(lines)
+            } else if line.starts_with(b">") {
+                quoted(lines)
+            } else {
+                None
+            }
+        },
+        _ => None,
+    }
+}
+
+/// Parse an admonition in the MkDocs syntax
+fn indented(lines: &[&[u8]]) -> Option<(Admonition, usize)> {
+    let header = trim_end(&unindent(lines[0], 3)[3 ..]);
+
+    if !header.starts_with(b" ") && !header.starts_with(b"\t") {
+        return None;
+    }
+
+    let header = unindent(header, 4);
+    let end = header.iter().position(|&c| c == b' ' || c == b'\t').unwrap_or(header.len());
+    let (kind, rest) = header.split_at(end);
+    let rest = unindent(rest, 4);
+
+    if !is_kind(kind) {
+        return None;
+    }
+
+    let title =
+        if rest.is_empty() {
+            None
+        } else if rest.len() >= 2 && rest.starts_with(b"\"") && rest.ends_with(b"\"") {
+            Some(rest[1 .. rest.len() - 1].to_vec())
+        } else {
+            return None;
+        };
+
+    let mut text = vec![];
+    let mut end = 1;
+    let mut i = 1;
+
+    while i < lines.len() {
+        if is_blank(lines[i]) {
+            text.push(b'\n');
+        } else if indentation(lines[i]) >= 4 {
+            text.extend_from_slice(unindent(lines[i], 4));
+            end = i + 1;
+        } else {
+            break;
+        }
+
+        i += 1;
+    }
+
+    // drop the trailing blank lines
+    text.truncate(text.len() - (i - end));
+
+    let admonition = Admonition {
+        kind: kind.to_ascii_lowercase(),
+        title: title,
+        text: text,
+    };
+
+    Some((admonition, end))
+}
+
+/// Parse an admonition in the GitHub syntax
+fn quoted(lines: &[&[u8]]) -> Option<(Admonition, usize)> {
+    let quote = |line: &[u8]| -> Option<Vec<u8>> {
+        if indentation(line) > 3 || is_blank(line) {
+            return None;
+        }
+
+        let line = unindent(line, 3);
+
+        if !line.starts_with(b">") {
+            return None;
+        }
+
+        let line = &line[1 ..];
+
+        match line.first() {
+            Some(&b' ') | Some(&b'\t') => Some(line[1 ..].to_vec()),
+            _ => Some(line.to_vec()),
+        }
+    };
+
+    let first = match quote(lines[0]) {
+        Some(first) => first,
+        None => return None,
+    };
+
+    let first = trim_end(&first);
+
+    if !first.starts_with(b"[!") || !first.ends_with(b"]") || !is_kind(&first[2 .. first.len() - 1]) {
+        return None;
+    }
+
+    let mut text = vec![];
+    let mut end = 1;
+
+    while let Some(line) = lines.get(end).and_then(|line| quote(line)) {
+        text.extend_from_slice(&line);
+        end += 1;
+    }
+
+    let admonition = Admonition {
+        kind: first[2 .. first.len() - 1].to_ascii_lowercase(),
+        title: None,
+        text: text,
+    };
+
+    Some((admonition, end))
+}
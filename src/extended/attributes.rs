@@ -0,0 +1,213 @@
+//! Header attribute parsing
+//!
+//! A header may end with an attribute block, e.g. `## Install {#install .important}`.
+//! The block is replaced with a placeholder tag, which hoedown passes to
+//! `html_span`, so it ends up at the end of the header's rendered content where
+//! it's recognized and removed. Like block placeholders, it includes the
+//! render's nonce.
+
+use std::io::Write;
+use std::str;
+
+use extensions::Extension;
+use renderer::Attributes;
+use super::source::{is_blank, is_list_item, indentation, trim_end};
+use super::position::is_atx_header;
+
+const PLACEHOLDER_SUFFIX: &[u8] = b">";
+
+fn is_name(name: &str) -> bool {
+    !name.is_empty() && name.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'-' || c == b'_' || c == b':')
+}
+
+/// Parse the contents of an attribute block, without its braces
+fn parse(block: &[u8]) -> Option<Attributes> {
+    let block = match str::from_utf8(block) {
+        Ok(block) => block.trim(),
+        Err(_) => return None,
+    };
+
+    let mut tokens = vec![];
+    let mut start = 0;
+    let mut quoted = false;
+
+    // split on whitespace outside of quoted values
+    for (i, c) in block.char_indices() {
+        if c == '"' {
+            quoted = !quoted;
+        } else if c.is_whitespace() && !quoted {
+            if i > start {
+                tokens.push(&block[start .. i]);
+            }
+
+            start = i + c.len_utf8();
+        }
+    }
+
+    if quoted {
+        return None;
+    }
+
+    if block.len() > start {
+        tokens.push(&block[start ..]);
+    }
+
+    let mut attributes = Attributes::default();
+
+    for token in tokens {
+        if token.starts_with('#') && is_name(&token[1 ..]) {
+            attributes.id = Some(String::from(&token[1 ..]));
+        } else if token.starts_with('.') && is_name(&token[1 ..]) {
+            attributes.classes.push(String::from(&token[1 ..]));
+        } else if let Some(equals) = token.find('=') {
+            let (key, value) = (&token[.. equals], &token[equals + 1 ..]);
+
+            let value =
+                if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+                    &value[1 .. value.len() - 1]
+                } else {
+                    value
+                };
+
+            if !is_name(key) {
+                return None;
+            }
+
+            attributes.pairs.push((String::from(key), String::from(value)));
+        } else {
+            return None;
+        }
+    }
+
+    if attributes == Attributes::default() {
+        None
+    } else {
+        Some(attributes)
+    }
+}
+
+/// Split a trailing attribute block from the text of a header
+fn split(text: &[u8]) -> Option<(&[u8], Attributes)> {
+    let text = trim_end(text);
+
+    if !text.ends_with(b"}") {
+        return None;
+    }
+
+    let open = match text.iter().rposition(|&c| c == b'{') {
+        Some(open) => open,
+        None => return None,
+    };
+
+    parse(&text[open + 1 .. text.len() - 1]).map(|attributes| (trim_end(&text[.. open]), attributes))
+}
+
+fn placeholder_prefix(nonce: &str) -> Vec<u8> {
+    format!("<{}-attributes-", nonce).into_bytes()
+}
+
+fn placeholder(nonce: &str, index: usize) -> Vec<u8> {
+    let mut placeholder = placeholder_prefix(nonce);
+    write!(placeholder, "{}", index).unwrap();
+    placeholder.extend_from_slice(PLACEHOLDER_SUFFIX);
+    placeholder
+}
+
+/// If the text is an attribute placeholder, the index of its attributes
+pub fn index(text: &[u8], nonce: &str) -> Option<usize> {
+    let prefix = placeholder_prefix(nonce);
+
+    if !text.starts_with(&prefix) || !text.ends_with(PLACEHOLDER_SUFFIX) || text.len() < prefix.len() + PLACEHOLDER_SUFFIX.len() {
+        return None;
+    }
+
+    let index = &text[prefix.len() .. text.len() - PLACEHOLDER_SUFFIX.len()];
+
+    str::from_utf8(index).ok().and_then(|i| i.parse().ok())
+}
+
+/// Split an attribute placeholder from the end of a header's rendered content
+pub fn strip<'a>(content: &'a [u8], nonce: &str) -> Option<(&'a [u8], usize)> {
+    let content = trim_end(content);
+    let prefix = placeholder_prefix(nonce);
+
+    content.windows(prefix.len())
+        .rposition(|w| w == &prefix[..])
+        .and_then(|start| index(&content[start ..], nonce).map(|index| (trim_end(&content[.. start]), index)))
+}
+
+/// Remove the closing sequence of hashes from the text of an atx header
+fn strip_closing(text: &[u8]) -> &[u8] {
+    let text = trim_end(text);
+    let closing = text.iter().rev().take_while(|&&c| c == b'#').count();
+
+    if closing > 0 && text[.. text.len() - closing].ends_with(b" ") {
+        trim_end(&text[.. text.len() - closing])
+    } else {
+        text
+    }
+}
+
+/// The length of any blockquote markers at the start of the line
+fn quote_prefix(line: &[u8]) -> usize {
+    let mut pos = 0;
+
+    loop {
+        let spaces = line[pos ..].iter().take(3).take_while(|&&c| c == b' ').count();
+
+        if line.get(pos + spaces) != Some(&b'>') {
+            return pos;
+        }
+
+        pos += spaces + 1;
+
+        if line.get(pos) == Some(&b' ') {
+            pos += 1;
+        }
+    }
+}
+
+/// Replace the attribute block of a header line with a placeholder
+///
+/// The line may be an atx header, or the text of a setext header if the
+/// following line is its underline. Headers within blockquotes are recognized.
+/// The rest of the line is kept as is.
+///
+/// Returns the rewritten line along with the attributes.
+pub fn rewrite(line: &[u8], next: Option<&[u8]>, extensions: Extension, nonce: &str, index: usize) -> Option<(Vec<u8>, Attributes)> {
+    let (prefix, text) = line.split_at(quote_prefix(line));
+    let next = next.map(|next| trim_end(&next[quote_prefix(next) ..]));
+
+    let is_underline = |next: &[u8]| {
+        !next.is_empty() && (next.iter().all(|&c| c == b'=') || next.iter().all(|&c| c == b'-'))
+    };
+
+    let atx = is_atx_header(text, extensions);
+    let setext = !is_blank(text) && indentation(text) < 4 && !is_list_item(text) && next.map_or(false, is_underline);
+
+    if !atx && !setext {
+        return None;
+    }
+
+    let text = if atx { strip_closing(text) } else { text };
+
+    split(text).map(|(text, attributes)| {
+        let text = if atx { strip_closing(text) } else { text };
+
+        let mut rewritten = prefix.to_vec();
+        rewritten.extend_from_slice(text);
+
+        // keep the space after the hashes of e.g. `## {#id}`, which `SPACE_HEADERS` requires
+        if atx && text.iter().all(|&c| c == b'#') {
+            rewritten.push(b' ');
+        }
+
+        rewritten.extend_from_slice(&placeholder(nonce, index));
+
+        if line.ends_with(b"\n") {
+            rewritten.push(b'\n');
+        }
+
+        (rewritten, attributes)
+    })
+}
@@ -0,0 +1,166 @@
+//! Definition list parsing
+//!
+//! Follows the PHP Markdown Extra syntax, where one or more terms are each on
+//! their own line, followed by one or more definitions that begin with a colon:
+//!
+//!```text
+//!Term
+//!:   Definition
+//!    that continues here
+//!
+//!    and has a second paragraph.
+//!```
+
+use super::source::{is_blank, indentation, unindent, trim_end};
+
+/// A definition list consisting of groups of terms and their definitions
+pub struct DefinitionList {
+    pub items: Vec<Item>,
+}
+
+/// One or more terms along with their definitions
+pub struct Item {
+    pub terms: Vec<Vec<u8>>,
+    pub definitions: Vec<Definition>,
+}
+
+/// The markdown of a definition
+pub struct Definition {
+    pub text: Vec<u8>,
+
+    /// Whether the definition should be rendered as blocks rather than inline
+    ///
+    /// This is the case if it's preceded by a blank line or consists of
+    /// multiple paragraphs.
+    pub block: bool,
+}
+
+/// If the line begins a definition, the offset of the definition's text
+fn definition_marker(line: &[u8]) -> Option<usize> {
+    if indentation(line) > 3 {
+        return None;
+    }
+
+    let rest = unindent(line, 3);
+    let offset = line.len() - rest.len();
+
+    if !rest.starts_with(b":") || !rest.get(1).map_or(false, |&c| c == b' ' || c == b'\t') {
+        return None;
+    }
+
+    let spaces = rest[1 ..].iter().take_while(|&&c| c == b' ' || c == b'\t').count();
+
+    Some(offset + 1 + spaces)
+}
+
+/// Whether the line can be a term rather than some other kind of block
+fn is_term(line: &[u8]) -> bool {
+    if is_blank(line) || indentation(line) > 0 || definition_marker(line).is_some() {
+        return false;
+    }
+
+    let marker = line.iter().take_while(|&&c| c.is_ascii_digit()).count();
+
+    match line[0] {
+        b'#' | b'>' | b'<' | b'|' | b'`' | b'~' => return false,
+        b'-' | b'*' | b'+' if line.get(1).map_or(true, |&c| c == b' ' || c == b'\t') => return false,
+        _ if marker > 0 && line.get(marker) == Some(&b'.') => return false,
+        _ => (),
+    }
+
+    // horizontal rules and setext header underlines
+    !trim_end(line).iter().all(|&c| c == b'-' || c == b'*' || c == b'_' || c == b'=' || c == b' ')
+}
+
+fn skip_blank(lines: &[&[u8]], mut i: usize) -> usize {
+    while i < lines.len() && is_blank(lines[i]) {
+        i += 1;
+    }
+
+    i
+}
+
+/// Parse a definition list at the start of the given lines
+///
+/// Returns the definition list along with the number of lines it spans,
+/// excluding any trailing blank lines.
+pub fn parse(lines: &[&[u8]]) -> Option<(DefinitionList, usize)> {
+    if !lines.first().map_or(false, |line| is_term(line)) {
+        return None;
+    }
+
+    let mut items = vec![];
+    let mut end = 0;
+
+    loop {
+        let start = skip_blank(lines, end);
+        let mut i = start;
+
+        while i < lines.len() && is_term(lines[i]) {
+            i += 1;
+        }
+
+        if i == start {
+            break;
+        }
+
+        let terms = lines[start .. i].iter().map(|line| trim_end(line).to_vec()).collect();
+
+        let mut next = skip_blank(lines, i);
+        let mut loose = next > i;
+        let mut definitions = vec![];
+
+        while let Some(offset) = lines.get(next).and_then(|line| definition_marker(line)) {
+            let mut text = lines[next][offset ..].to_vec();
+            let mut block = loose;
+
+            i = next + 1;
+
+            loop {
+                // lazy or indented continuation lines
+                while i < lines.len() && !is_blank(lines[i]) && definition_marker(lines[i]).is_none() {
+                    text.extend_from_slice(unindent(lines[i], 4));
+                    i += 1;
+                }
+
+                // subsequent paragraphs must be indented
+                let k = skip_blank(lines, i);
+
+                if k > i && k < lines.len() && indentation(lines[k]) >= 4 {
+                    for _ in i .. k {
+                        text.push(b'\n');
+                    }
+
+                    block = true;
+                    i = k;
+                } else {
+                    break;
+                }
+            }
+
+            definitions.push(Definition {
+                text: text,
+                block: block,
+            });
+
+            end = i;
+            next = skip_blank(lines, i);
+            loose = next > i;
+        }
+
+        if definitions.is_empty() {
+            break;
+        }
+
+        items.push(Item {
+            terms: terms,
+            definitions: definitions,
+        });
+    }
+
+    if items.is_empty() {
+        None
+    } else {
+        Some((DefinitionList { items: items }, end))
+    }
+}
@@ -0,0 +1,493 @@
+//! Extensions implemented by these bindings
+//!
+//! Hoedown can't be taught new syntax, so extensions that it doesn't know about
+//! are implemented by interposing this renderer between hoedown and the user's
+//! renderer. It post-processes the callbacks it receives before delegating to
+//! the user's renderer.
+
+use std::io::Write;
+use std::mem;
+
+use buffer::Buffer;
+use extensions::{
+    Extension,
+    FOOTNOTES,
+    TASK_LISTS,
+    DEFINITION_LISTS,
+    ADMONITIONS,
+    WIKI_LINKS,
+    HEADER_ATTRIBUTES,
+    SOURCE_POSITIONS,
+};
+use markdown::Markdown;
+use document::Document;
+use renderer::{Render, Attributes, SourcePosition, AutoLink, Table, list};
+
+use self::source::Block;
+use self::definition_list::DefinitionList;
+use self::admonition::Admonition;
+use self::wiki_link::WikiLink;
+
+pub(crate) mod source;
+mod definition_list;
+mod admonition;
+mod wiki_link;
+mod attributes;
+mod task_list;
+pub(crate) mod position;
+
+/// The extensions that are implemented by these bindings
+pub fn extensions() -> Extension {
+    TASK_LISTS | DEFINITION_LISTS | ADMONITIONS | WIKI_LINKS | HEADER_ATTRIBUTES | SOURCE_POSITIONS
+}
+
+/// Whether the given extensions require rendering through `Extended`
+pub fn is_required(extensions: Extension) -> bool {
+    extensions.intersects(self::extensions())
+}
+
+/// Renderer that implements the bindings-level extensions on top of another renderer
+pub struct Extended<'a, R: 'a> {
+    base: &'a mut R,
+    extensions: Extension,
+    max_nesting: usize,
+
+    /// The nonce included in placeholders, which doesn't occur in the document
+    nonce: String,
+
+    /// Blocks found while preprocessing, taken as their placeholders are rendered
+    blocks: Vec<Option<Block>>,
+
+    /// The document's link reference definitions, made available to fragments
+    references: Vec<u8>,
+
+    /// Wiki links found while rewriting, referred to by their placeholder urls
+    wiki_links: Vec<WikiLink>,
+
+    /// Header attributes found while preprocessing, taken as their headers are rendered
+    attributes: Vec<Option<Attributes>>,
+
+    /// Task markers found while preprocessing, taken as their list items are rendered
+    tasks: Vec<Option<Vec<u8>>>,
+
+    /// The offset in the output at which the document begins
+    start: usize,
+
+    /// The number of fragments currently being rendered
+    depth: usize,
+
+    /// Whether paragraphs should be rendered as their bare content
+    inline: bool,
+
+    /// The positions of the document's top-level blocks, while rendering with
+    /// source positions
+    positions: Vec<SourcePosition>,
+
+    /// The offset in the output at which the current top-level block begins
+    block_start: usize,
+}
+
+impl<'a, R> Extended<'a, R> where R: Render {
+    pub fn new(base: &'a mut R, extensions: Extension) -> Extended<'a, R> {
+        Extended {
+            base: base,
+            extensions: extensions,
+            max_nesting: 16,
+            nonce: String::new(),
+            blocks: vec![],
+            references: vec![],
+            wiki_links: vec![],
+            attributes: vec![],
+            tasks: vec![],
+            start: 0,
+            depth: 0,
+            inline: false,
+            positions: vec![],
+            block_start: 0,
+        }
+    }
+
+    /// Render a fragment of the document with this renderer
+    ///
+    /// The document's link references are available to the fragment. If `inline`
+    /// is set, paragraphs are rendered as their bare content.
+    ///
+    /// Footnotes aren't supported within fragments, since they would be
+    /// collected separately from those of the document.
+    fn render_fragment(&mut self, text: &[u8], inline: bool) -> Buffer {
+        let mut contents = Buffer::new(64);
+        contents.write_all(text).unwrap();
+        contents.write_all(b"\n\n").unwrap();
+        contents.write_all(&self.references).unwrap();
+
+        let fragment =
+            Markdown::from(contents)
+            .extensions(self.extensions - FOOTNOTES)
+            .max_nesting(self.max_nesting);
+
+        let mut output = Buffer::new(64);
+        let inline = mem::replace(&mut self.inline, inline);
+
+        self.depth += 1;
+        self.render_to(&fragment, &mut output);
+        self.depth -= 1;
+
+        self.inline = inline;
+
+        output
+    }
+
+    /// Rewrite the wiki links in the text, if the extension is enabled
+    ///
+    /// Code blocks are skipped if `blocks` is set.
+    fn rewrite_wiki_links(&mut self, text: &[u8], blocks: bool) -> Option<Vec<u8>> {
+        if !self.extensions.contains(WIKI_LINKS) {
+            return None;
+        }
+
+        let (text, links) = wiki_link::rewrite(text, self.extensions, blocks, &self.nonce, self.wiki_links.len());
+        self.wiki_links.extend(links);
+
+        Some(text)
+    }
+
+    /// Undo the rewriting of wiki links in text that isn't parsed for links
+    fn restore_wiki_links(&self, text: Option<&Buffer>) -> Option<Buffer> {
+        text.and_then(|t| wiki_link::restore(t, &self.nonce, &self.wiki_links))
+            .map(|restored| Buffer::from(&restored[..]))
+    }
+
+    /// Render the document, reporting the output of each of its top-level
+    /// blocks along with their positions
+    ///
+    /// The document is rendered as a whole, with a marker before each block.
+    /// Anything rendered outside of the blocks, such as the footnotes, is
+    /// written to the output as is.
+    fn render_positioned(&mut self, input: &Markdown, output: &mut Buffer) {
+        self.nonce = source::nonce(&input.contents);
+        self.positions = position::blocks(&input.contents, input.extensions);
+        self.block_start = output.len();
+
+        let marked =
+            Markdown::from(&position::mark(&input.contents, &self.positions, &self.nonce)[..])
+            .extensions(input.extensions - SOURCE_POSITIONS)
+            .max_nesting(input.max_nesting);
+
+        self.render_document(&marked, output);
+        self.positions.clear();
+    }
+
+    /// Report the output of the block before the marker with the given index,
+    /// which is the output since the previous marker
+    fn end_positioned_block(&mut self, output: &mut Buffer, index: usize) {
+        if let Some(position) = index.checked_sub(1).and_then(|i| self.positions.get(i)).cloned() {
+            let mut content = Buffer::from(&output[self.block_start ..]);
+            output.truncate(self.block_start);
+
+            // the separator from the previous block's output
+            if content.starts_with(b"\n") {
+                content = Buffer::from(&content[1 ..]);
+            }
+
+            // e.g. reference definitions render nothing, but are still reported
+            self.base.positioned_block(output, Some(&content), &position);
+        }
+
+        self.block_start = output.len();
+    }
+
+    /// Render the document, or a fragment of it, once its nonce is known
+    fn render_document(&mut self, input: &Markdown, output: &mut Buffer) {
+        let source = source::preprocess(&input.contents, input.extensions, &self.nonce, self.blocks.len(), self.attributes.len(), self.tasks.len());
+
+        self.blocks.extend(source.blocks.into_iter().map(Some));
+        self.attributes.extend(source.attributes.into_iter().map(Some));
+        self.tasks.extend(source.tasks.into_iter().map(Some));
+        self.max_nesting = input.max_nesting;
+
+        if self.depth == 0 {
+            self.references = source.references;
+        }
+
+        let text = self.rewrite_wiki_links(&source.text, true).unwrap_or(source.text);
+
+        let renderer = unsafe { self.to_hoedown() };
+        let doc = Document::new(&renderer, input.extensions, input.max_nesting);
+        doc.render(&text, output);
+    }
+
+    fn render_block(&mut self, output: &mut Buffer, block: Block) {
+        match block {
+            Block::DefinitionList(list) => self.definition_list_block(output, list),
+            Block::Admonition(admonition) => self.admonition_block(output, admonition),
+        }
+    }
+
+    fn definition_list_block(&mut self, output: &mut Buffer, list: DefinitionList) {
+        let mut content = Buffer::new(64);
+
+        for item in list.items {
+            for term in item.terms {
+                let term = self.render_fragment(&term, true);
+                self.base.definition_term(&mut content, Some(&term));
+            }
+
+            for definition in item.definitions {
+                let data = self.render_fragment(&definition.text, !definition.block);
+                self.base.definition_data(&mut content, Some(&data));
+            }
+        }
+
+        self.base.definition_list(output, Some(&content));
+    }
+
+    fn admonition_block(&mut self, output: &mut Buffer, admonition: Admonition) {
+        let title = admonition.title.map(|title| self.render_fragment(&title, true));
+        let content = self.render_fragment(&admonition.text, false);
+        let kind = Buffer::from(&admonition.kind[..]);
+
+        self.base.admonition(output, Some(&content), &kind, title.as_ref());
+    }
+}
+
+impl<'a, R> Render for Extended<'a, R> where R: Render {
+    fn render_to(&mut self, input: &Markdown, output: &mut Buffer) {
+        if self.depth == 0 && input.extensions.contains(SOURCE_POSITIONS) {
+            return self.render_positioned(input, output);
+        }
+
+        if self.depth == 0 {
+            self.nonce = source::nonce(&input.contents);
+        }
+
+        self.render_document(input, output)
+    }
+
+    fn render_inline_to(&mut self, input: &Markdown, output: &mut Buffer) {
+        if self.depth == 0 {
+            self.nonce = source::nonce(&input.contents);
+        }
+
+        let text = self.rewrite_wiki_links(&input.contents, false);
+
+        let renderer = unsafe { self.to_hoedown() };
+        let doc = Document::new(&renderer, input.extensions, input.max_nesting);
+        doc.render_inline(text.as_ref().map_or(&input.contents[..], |t| &t[..]), output);
+    }
+
+    // block-level: not registered = skip the block
+    fn code_block(&mut self, output: &mut Buffer, text: Option<&Buffer>, lang: Option<&Buffer>) {
+        let restored = self.restore_wiki_links(text);
+        self.base.code_block(output, restored.as_ref().or(text), lang)
+    }
+    fn quote_block(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        self.base.quote_block(output, content)
+    }
+    fn header(&mut self, output: &mut Buffer, content: Option<&Buffer>, level: i32) {
+        if let Some((content, index)) = content.and_then(|c| attributes::strip(c, &self.nonce)) {
+            let content = Buffer::from(content);
+
+            return match self.attributes.get_mut(index).and_then(|a| a.take()) {
+                Some(attributes) => self.base.header_with_attributes(output, Some(&content), level, &attributes),
+                None => self.base.header(output, Some(&content), level),
+            };
+        }
+
+        self.base.header(output, content, level)
+    }
+    fn header_with_attributes(&mut self, output: &mut Buffer, content: Option<&Buffer>, level: i32, attributes: &Attributes) {
+        self.base.header_with_attributes(output, content, level, attributes)
+    }
+    fn horizontal_rule(&mut self, output: &mut Buffer) {
+        self.base.horizontal_rule(output)
+    }
+    fn list(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: list::List) {
+        self.base.list(output, content, flags)
+    }
+    fn list_item(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: list::List) {
+        if let Some((index, content)) = content.and_then(|c| task_list::strip(c, &self.nonce)) {
+            if let Some(marker) = self.tasks.get_mut(index).and_then(|m| m.take()) {
+                let flags =
+                    if task_list::is_checked(&marker) { flags | list::TASK | list::CHECKED }
+                    else { flags | list::TASK };
+
+                return self.base.list_item(output, Some(&content), flags);
+            }
+        }
+
+        self.base.list_item(output, content, flags)
+    }
+    fn paragraph(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        if self.inline {
+            content.map(|c| output.pipe(c));
+        } else {
+            self.base.paragraph(output, content)
+        }
+    }
+    fn table(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        self.base.table(output, content)
+    }
+    fn table_header(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        self.base.table_header(output, content)
+    }
+    fn table_body(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        self.base.table_body(output, content)
+    }
+    fn table_row(&mut self, output: &mut Buffer, content: Option<&Buffer>, columns: usize) {
+        self.base.table_row(output, content, columns)
+    }
+    fn table_cell(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: Table, column: usize, columns: usize) {
+        self.base.table_cell(output, content, flags, column, columns)
+    }
+    fn footnotes(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        self.base.footnotes(output, content)
+    }
+    fn footnote_definition(&mut self, output: &mut Buffer, content: Option<&Buffer>, num: u32) {
+        self.base.footnote_definition(output, content, num)
+    }
+    fn html_block(&mut self, output: &mut Buffer, text: Option<&Buffer>) {
+        if let Some(index) = text.and_then(|t| position::marker(t, &self.nonce)) {
+            if self.depth == 0 {
+                return self.end_positioned_block(output, index);
+            }
+        }
+
+        let block = text.and_then(|t| source::placeholder(t, &self.nonce))
+            .and_then(|index| self.blocks.get_mut(index).and_then(|b| b.take()));
+
+        match block {
+            Some(block) => self.render_block(output, block),
+            None => {
+                let restored = self.restore_wiki_links(text);
+                self.base.html_block(output, restored.as_ref().or(text))
+            },
+        }
+    }
+    fn positioned_block(&mut self, output: &mut Buffer, content: Option<&Buffer>, position: &SourcePosition) {
+        self.base.positioned_block(output, content, position)
+    }
+    fn definition_list(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        self.base.definition_list(output, content)
+    }
+    fn definition_term(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        self.base.definition_term(output, content)
+    }
+    fn definition_data(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        self.base.definition_data(output, content)
+    }
+    fn admonition(&mut self, output: &mut Buffer, content: Option<&Buffer>, kind: &Buffer, title: Option<&Buffer>) {
+        self.base.admonition(output, content, kind, title)
+    }
+
+    // span-level: not registered = pass-through
+    fn autolink(&mut self, output: &mut Buffer, link: Option<&Buffer>, link_type: AutoLink) -> bool {
+        self.base.autolink(output, link, link_type)
+    }
+    fn code_span(&mut self, output: &mut Buffer, text: Option<&Buffer>) -> bool {
+        let restored = self.restore_wiki_links(text);
+        self.base.code_span(output, restored.as_ref().or(text))
+    }
+    fn double_emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.base.double_emphasis(output, content)
+    }
+    fn emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.base.emphasis(output, content)
+    }
+    fn underline(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.base.underline(output, content)
+    }
+    fn highlight(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.base.highlight(output, content)
+    }
+    fn quote_span(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.base.quote_span(output, content)
+    }
+    fn image(&mut self, output: &mut Buffer, link: Option<&Buffer>, title: Option<&Buffer>, alt: Option<&Buffer>) -> bool {
+        self.base.image(output, link, title, alt)
+    }
+    fn line_break(&mut self, output: &mut Buffer) -> bool {
+        self.base.line_break(output)
+    }
+    fn link(&mut self, output: &mut Buffer, content: Option<&Buffer>, link: Option<&Buffer>, title: Option<&Buffer>) -> bool {
+        let wiki_links = &self.wiki_links;
+        let index = link.and_then(|l| wiki_link::index(l, &self.nonce));
+
+        if let Some(wiki) = index.and_then(|i| wiki_links.get(i)) {
+            let target = Buffer::from(&wiki.target[..]);
+
+            if !self.base.wiki_link(output, &target, content) {
+                output.write_all(&wiki.original).unwrap();
+            }
+
+            return true;
+        }
+
+        self.base.link(output, content, link, title)
+    }
+    fn wiki_link(&mut self, output: &mut Buffer, target: &Buffer, label: Option<&Buffer>) -> bool {
+        self.base.wiki_link(output, target, label)
+    }
+    fn pattern_link(&mut self, output: &mut Buffer, text: &Buffer, link: &Buffer, name: &str) -> bool {
+        self.base.pattern_link(output, text, link, name)
+    }
+    fn triple_emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.base.triple_emphasis(output, content)
+    }
+    fn strikethrough(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.base.strikethrough(output, content)
+    }
+    fn superscript(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.base.superscript(output, content)
+    }
+    fn footnote_reference(&mut self, output: &mut Buffer, num: u32) -> bool {
+        self.base.footnote_reference(output, num)
+    }
+    fn math(&mut self, output: &mut Buffer, text: Option<&Buffer>, displaymode: i32) -> bool {
+        let restored = self.restore_wiki_links(text);
+        self.base.math(output, restored.as_ref().or(text), displaymode)
+    }
+    fn html_span(&mut self, output: &mut Buffer, text: Option<&Buffer>) -> bool {
+        // keep attribute and task placeholders for the header or list item to
+        // find, whatever the html flags
+        let is_placeholder = |t: &&Buffer| {
+            attributes::index(t, &self.nonce).is_some()
+                || task_list::index(t, &self.nonce).map_or(false, |(_, len)| len == t.len())
+        };
+
+        if let Some(text) = text.filter(is_placeholder) {
+            output.pipe(text);
+            return true;
+        }
+
+        let restored = self.restore_wiki_links(text);
+        self.base.html_span(output, restored.as_ref().or(text))
+    }
+
+    // low-level: not registered = pass-through
+    fn entity(&mut self, output: &mut Buffer, text: Option<&Buffer>) {
+        self.base.entity(output, text)
+    }
+    fn normal_text(&mut self, output: &mut Buffer, text: Option<&Buffer>) {
+        self.base.normal_text(output, text)
+    }
+
+    // misc callbacks
+    fn before_render(&mut self, output: &mut Buffer, inline_render: bool) {
+        if self.depth == 0 {
+            self.start = output.len();
+            self.base.before_render(output, inline_render)
+        }
+    }
+    fn after_render(&mut self, output: &mut Buffer, inline_render: bool) {
+        if self.depth == 0 {
+            if self.tasks.iter().any(Option::is_some) {
+                if let Some(restored) = task_list::restore(&output[self.start ..], &self.nonce, &self.tasks) {
+                    output.truncate(self.start);
+                    output.write_all(&restored).unwrap();
+                }
+            }
+
+            self.base.after_render(output, inline_render)
+        }
+    }
+}
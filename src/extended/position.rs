@@ -0,0 +1,235 @@
+//! Source position tracking
+//!
+//! Hoedown doesn't report where in the input its callbacks come from, so the
+//! document is instead split into its top-level blocks, and a marker is
+//! inserted before each of them. The document is rendered as a whole, and its
+//! output is split where the markers are rendered. Lines are only split where
+//! doing so can't change the rendering, so a group of blocks that aren't
+//! separated by a blank line, such as a paragraph directly followed by a list,
+//! are reported as one.
+//!
+//! Only top-level blocks have positions: nested blocks and spans don't.
+
+use std::io::Write;
+use std::ops::Range;
+
+use extensions::{Extension, FENCED_CODE, SPACE_HEADERS};
+use renderer::SourcePosition;
+use super::source::{lines, is_blank, is_list_item, trim_end, indentation, unindent, fence, code_lines};
+
+/// Whether the line is an atx header
+///
+/// As in hoedown, `SPACE_HEADERS` requires a space after at most six hashes.
+pub fn is_atx_header(line: &[u8], extensions: Extension) -> bool {
+    let hashes = line.iter().take(6).take_while(|&&c| c == b'#').count();
+
+    hashes > 0 && (!extensions.contains(SPACE_HEADERS) || line.get(hashes).map_or(true, |&c| c == b' '))
+}
+
+/// The name of the html tag that the line opens, if any
+fn html_tag(line: &[u8]) -> Option<&[u8]> {
+    if !line.starts_with(b"<") {
+        return None;
+    }
+
+    let name = line[1 ..].iter().take_while(|c| c.is_ascii_alphanumeric()).count();
+
+    if name == 0 { None } else { Some(&line[1 .. 1 + name]) }
+}
+
+/// The tags that hoedown recognizes as the start of an html block
+const BLOCK_TAGS: &[&[u8]] = &[
+    b"blockquote", b"del", b"div", b"dl", b"fieldset", b"figure", b"form",
+    b"h1", b"h2", b"h3", b"h4", b"h5", b"h6", b"hr", b"iframe", b"ins",
+    b"math", b"noscript", b"ol", b"p", b"pre", b"script", b"style", b"table", b"ul",
+];
+
+/// Whether the line starts an html block, rather than a paragraph
+pub fn is_html_block(line: &[u8]) -> bool {
+    line.starts_with(b"<!--")
+        || html_tag(line).map_or(false, |tag| BLOCK_TAGS.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+}
+
+/// Whether the text contains the closing tag
+fn closes(text: &[u8], tag: &[u8]) -> bool {
+    let mut closing = b"</".to_vec();
+    closing.extend_from_slice(tag);
+
+    text.windows(closing.len()).any(|w| w.eq_ignore_ascii_case(&closing))
+}
+
+/// Which of the lines are within code blocks or html blocks, where markdown
+/// syntax isn't recognized
+pub fn literal_lines(input: &[u8], extensions: Extension) -> Vec<bool> {
+    let lines = lines(input);
+    let mut literal = code_lines(&lines, extensions);
+
+    for block in blocks(input, extensions) {
+        if is_html_block(unindent(lines[block.start_line - 1], 3)) {
+            for line in block.start_line - 1 .. block.end_line {
+                literal[line] = true;
+            }
+        }
+    }
+
+    literal
+}
+
+/// The offsets at which each of the lines begins
+pub fn offsets(lines: &[&[u8]]) -> Vec<usize> {
+    lines.iter().scan(0, |offset, line| {
+        let start = *offset;
+        *offset += line.len();
+        Some(start)
+    }).collect()
+}
+
+/// The position of the given byte range, given the offsets at which each line begins
+pub fn position(offsets: &[usize], range: Range<usize>) -> SourcePosition {
+    let line = |offset: usize| match offsets.binary_search(&offset) {
+        Ok(line) => line,
+        Err(line) => line - 1,
+    };
+
+    let last = if range.end > range.start { range.end - 1 } else { range.start };
+    let (start_line, end_line) = (line(range.start), line(last));
+
+    SourcePosition {
+        start_line: start_line + 1,
+        start_column: range.start - offsets[start_line] + 1,
+        end_line: end_line + 1,
+        end_column: last - offsets[end_line] + 1,
+        range: range,
+    }
+}
+
+/// Split the input into its top-level blocks, returning their byte ranges
+/// along with their positions
+///
+/// Leading indentation and trailing blank lines aren't included in a block.
+pub fn blocks(input: &[u8], extensions: Extension) -> Vec<SourcePosition> {
+    let lines = lines(input);
+
+    let offsets = offsets(&lines);
+
+    let mut blocks = vec![];
+    let mut i = 0;
+
+    while i < lines.len() {
+        if is_blank(lines[i]) {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        let first = lines[start];
+        let html = html_tag(first);
+        let mut fenced: Option<(u8, usize)> = None;
+
+        loop {
+            let line = lines[i];
+
+            match fenced {
+                Some((c, len)) => match fence(line) {
+                    Some((d, l)) if d == c && l >= len && is_blank(&unindent(line, 3)[l ..]) => fenced = None,
+                    _ => (),
+                },
+                None if extensions.contains(FENCED_CODE) => fenced = fence(line),
+                None => (),
+            }
+
+            i += 1;
+
+            if i >= lines.len() {
+                break;
+            }
+
+            if fenced.is_some() {
+                continue;
+            }
+
+            let text = &input[offsets[start] .. offsets[i]];
+            let open = html.map_or(false, |tag| !closes(text, tag));
+
+            // atx headers are always a single line
+            if !open && (is_atx_header(first, extensions) || is_atx_header(lines[i], extensions)) {
+                break;
+            }
+
+            if !is_blank(lines[i]) {
+                continue;
+            }
+
+            let next = match lines[i ..].iter().position(|line| !is_blank(line)) {
+                Some(next) => i + next,
+                None => break,
+            };
+
+            let continues =
+                open
+                || indentation(lines[next]) > 0
+                || (is_list_item(first) && is_list_item(lines[next]))
+                || (first.starts_with(b">") && lines[next].starts_with(b">"))
+                || lines[next].starts_with(b": ");
+
+            if !continues {
+                break;
+            }
+
+            i = next;
+        }
+
+        // exclude the leading indentation and any trailing blank lines
+        let end = (start .. i).rev().find(|&j| !is_blank(lines[j])).unwrap_or(start);
+        let range = offsets[start] + (first.len() - unindent(first, 3).len()) .. offsets[end] + trim_end(lines[end]).len();
+
+        blocks.push(position(&offsets, range));
+    }
+
+    blocks
+}
+
+fn marker_prefix(nonce: &str) -> Vec<u8> {
+    format!("<!--{}-position:", nonce).into_bytes()
+}
+
+/// Insert a marker before each of the blocks, and after the last one
+///
+/// Each marker is an html block of its own, which includes the nonce.
+pub fn mark(input: &[u8], blocks: &[SourcePosition], nonce: &str) -> Vec<u8> {
+    let mut marked = Vec::with_capacity(input.len() + 32 * (blocks.len() + 1));
+    let mut pos = 0;
+
+    for (i, block) in blocks.iter().enumerate() {
+        // the marker goes before the block's leading indentation
+        let start = block.range.start - (block.start_column - 1);
+
+        marked.extend_from_slice(&input[pos .. start]);
+        marked.extend_from_slice(b"\n\n");
+        marked.extend_from_slice(&marker_prefix(nonce));
+        write!(marked, "{}-->\n\n", i).unwrap();
+
+        pos = start;
+    }
+
+    marked.extend_from_slice(&input[pos ..]);
+    marked.extend_from_slice(b"\n\n");
+    marked.extend_from_slice(&marker_prefix(nonce));
+    write!(marked, "{}-->\n\n", blocks.len()).unwrap();
+
+    marked
+}
+
+/// Find the index of the marker in the text of an html block
+pub fn marker(text: &[u8], nonce: &str) -> Option<usize> {
+    let text = trim_end(text);
+    let prefix = marker_prefix(nonce);
+
+    if !text.starts_with(&prefix) || !text.ends_with(b"-->") || text.len() < prefix.len() + 3 {
+        return None;
+    }
+
+    let index = &text[prefix.len() .. text.len() - 3];
+
+    ::std::str::from_utf8(index).ok().and_then(|i| i.parse().ok())
+}
@@ -0,0 +1,333 @@
+//! Source-level processing for block extensions
+//!
+//! Blocks that hoedown can't parse are found before rendering and replaced
+//! with placeholder html comments, which are then rendered in their place
+//! when hoedown reports them as html blocks. The placeholder is padded with
+//! blank lines so that the line numbers of the rest of the document are kept.
+//!
+//! Placeholders include a nonce that doesn't occur in the document, so that a
+//! document can't contain a placeholder of its own.
+
+use std::io::Write;
+
+use extensions::{Extension, FENCED_CODE, DISABLE_INDENTED_CODE, TASK_LISTS, DEFINITION_LISTS, ADMONITIONS, HEADER_ATTRIBUTES};
+use renderer::Attributes;
+use super::definition_list::{self, DefinitionList};
+use super::admonition::{self, Admonition};
+use super::attributes;
+use super::task_list;
+use super::position;
+
+const PLACEHOLDER_SUFFIX: &[u8] = b"-->";
+
+/// A block that is rendered by these bindings
+pub enum Block {
+    DefinitionList(DefinitionList),
+    Admonition(Admonition),
+}
+
+/// A document whose extension blocks have been replaced with placeholders
+pub struct Source {
+    /// The document with placeholders in place of the blocks
+    pub text: Vec<u8>,
+
+    /// The blocks that were found, in the order of their placeholders
+    pub blocks: Vec<Block>,
+
+    /// The document's link reference definitions, one per line
+    pub references: Vec<u8>,
+
+    /// The attributes of headers, in the order of their placeholders
+    pub attributes: Vec<Attributes>,
+
+    /// The markers of task list items, in the order of their placeholders
+    pub tasks: Vec<Vec<u8>>,
+}
+
+/// Split the input into lines, each including its line ending
+pub fn lines(input: &[u8]) -> Vec<&[u8]> {
+    let mut lines = vec![];
+    let mut start = 0;
+
+    while start < input.len() {
+        let end = input[start ..].iter().position(|&c| c == b'\n').map_or(input.len(), |i| start + i + 1);
+        lines.push(&input[start .. end]);
+        start = end;
+    }
+
+    lines
+}
+
+/// Whether the line consists only of whitespace
+pub fn is_blank(line: &[u8]) -> bool {
+    line.iter().all(|&c| c == b' ' || c == b'\t' || c == b'\r' || c == b'\n')
+}
+
+/// Remove trailing whitespace, including the line ending
+pub fn trim_end(line: &[u8]) -> &[u8] {
+    &line[.. line.iter().rposition(|&c| !is_blank(&[c])).map_or(0, |i| i + 1)]
+}
+
+/// The number of leading spaces, counting tabs as four
+pub fn indentation(line: &[u8]) -> usize {
+    let mut width = 0;
+
+    for &c in line {
+        match c {
+            b' ' => width += 1,
+            b'\t' => width += 4,
+            _ => break,
+        }
+    }
+
+    width
+}
+
+/// Remove up to `width` columns of indentation from the line
+pub fn unindent(line: &[u8], width: usize) -> &[u8] {
+    let mut removed = 0;
+    let mut pos = 0;
+
+    while pos < line.len() && removed < width {
+        match line[pos] {
+            b' ' => removed += 1,
+            b'\t' => removed += 4,
+            _ => break,
+        }
+
+        pos += 1;
+    }
+
+    &line[pos ..]
+}
+
+/// If the line opens or closes a code fence, its fence character and length
+pub fn fence(line: &[u8]) -> Option<(u8, usize)> {
+    if indentation(line) > 3 {
+        return None;
+    }
+
+    let line = unindent(line, 3);
+
+    match line.first() {
+        Some(&c) if c == b'`' || c == b'~' => {
+            let len = line.iter().take_while(|&&d| d == c).count();
+
+            if len >= 3 { Some((c, len)) } else { None }
+        },
+        _ => None,
+    }
+}
+
+/// Whether the line begins a list item
+pub fn is_list_item(line: &[u8]) -> bool {
+    if indentation(line) > 3 {
+        return false;
+    }
+
+    let line = unindent(line, 3);
+    let digits = line.iter().take_while(|&&c| c.is_ascii_digit()).count();
+
+    let marker = match line.first() {
+        Some(&b'-') | Some(&b'*') | Some(&b'+') => 1,
+        _ if digits > 0 && line.get(digits) == Some(&b'.') => digits + 1,
+        _ => return false,
+    };
+
+    line.get(marker).map_or(true, |&c| c == b' ' || c == b'\t' || c == b'\n')
+}
+
+/// Which of the lines are within code blocks
+///
+/// These are fenced code blocks, if enabled, and indented code blocks outside
+/// of lists, where indentation continues the list item instead.
+pub fn code_lines(lines: &[&[u8]], extensions: Extension) -> Vec<bool> {
+    let mut code = Vec::with_capacity(lines.len());
+    let mut fenced: Option<(u8, usize)> = None;
+    let mut indented = false;
+    let mut in_list = false;
+    let mut previous_blank = true;
+
+    for &line in lines {
+        let is_code =
+            if let Some((c, len)) = fenced {
+                match fence(line) {
+                    Some((d, l)) if d == c && l >= len && is_blank(&unindent(line, 3)[l ..]) => fenced = None,
+                    _ => (),
+                }
+
+                true
+            } else if extensions.contains(FENCED_CODE) && fence(line).is_some() {
+                fenced = fence(line);
+                true
+            } else if is_blank(line) {
+                indented
+            } else if indentation(line) > 3 {
+                indented = indented || (previous_blank && !in_list && !extensions.contains(DISABLE_INDENTED_CODE));
+                indented
+            } else {
+                indented = false;
+
+                if is_list_item(line) {
+                    in_list = true;
+                } else if previous_blank {
+                    in_list = false;
+                }
+
+                false
+            };
+
+        previous_blank = is_blank(line);
+        code.push(is_code);
+    }
+
+    code
+}
+
+/// Whether the line is a link reference definition, e.g. `[id]: http://example.com`
+fn is_reference(line: &[u8]) -> bool {
+    if indentation(line) > 3 {
+        return false;
+    }
+
+    let line = unindent(line, 3);
+
+    if !line.starts_with(b"[") || line.starts_with(b"[^") {
+        return false;
+    }
+
+    match line.iter().position(|&c| c == b']') {
+        Some(end) => end > 1 && line.get(end + 1) == Some(&b':'),
+        None => false,
+    }
+}
+
+/// A nonce for the placeholders of the input, which doesn't occur in it
+pub fn nonce(input: &[u8]) -> String {
+    (0..).map(|n| format!("hoedown-{}", n))
+        .find(|nonce| !input.windows(nonce.len()).any(|w| w == nonce.as_bytes()))
+        .unwrap()
+}
+
+fn placeholder_prefix(nonce: &str) -> Vec<u8> {
+    format!("<!--{}-block:", nonce).into_bytes()
+}
+
+/// Find the index of the block referred to by a placeholder
+pub fn placeholder(text: &[u8], nonce: &str) -> Option<usize> {
+    let text = trim_end(text);
+    let prefix = placeholder_prefix(nonce);
+
+    if !text.starts_with(&prefix) || !text.ends_with(PLACEHOLDER_SUFFIX) || text.len() < prefix.len() + PLACEHOLDER_SUFFIX.len() {
+        return None;
+    }
+
+    let index = &text[prefix.len() .. text.len() - PLACEHOLDER_SUFFIX.len()];
+
+    ::std::str::from_utf8(index).ok().and_then(|i| i.parse().ok())
+}
+
+/// Find the blocks in the input that are implemented by these bindings
+///
+/// Blocks are only recognized at the top level of the document, outside of
+/// code blocks and html blocks, and must be preceded by a blank line or the
+/// start of the document.
+///
+/// Task markers are only recognized on lines that begin a list item within a
+/// list, or after a blank line, since hoedown otherwise continues a paragraph.
+///
+/// Placeholders are numbered starting from `first_block`, `first_attributes`
+/// and `first_task`, and include the given nonce.
+pub fn preprocess(input: &[u8], extensions: Extension, nonce: &str, first_block: usize, first_attributes: usize, first_task: usize) -> Source {
+    let lines = lines(input);
+    let literal = position::literal_lines(input, extensions);
+
+    let mut source = Source {
+        text: Vec::with_capacity(input.len()),
+        blocks: vec![],
+        references: vec![],
+        attributes: vec![],
+        tasks: vec![],
+    };
+
+    let mut in_list = false;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let after_blank = i == 0 || is_blank(lines[i - 1]);
+
+        if task_list::is_item(line) {
+            in_list = in_list || after_blank;
+        } else if !is_blank(line) && indentation(line) == 0 && after_blank {
+            in_list = false;
+        }
+
+        if literal[i] {
+            source.text.extend_from_slice(line);
+            i += 1;
+            continue;
+        }
+
+        if is_reference(line) {
+            source.references.extend_from_slice(line);
+
+            if !line.ends_with(b"\n") {
+                source.references.push(b'\n');
+            }
+        } else if after_blank {
+            let mut found = None;
+
+            if extensions.contains(ADMONITIONS) {
+                found = admonition::parse(&lines[i ..]).map(|(admonition, n)| (Block::Admonition(admonition), n));
+            }
+
+            if found.is_none() && extensions.contains(DEFINITION_LISTS) {
+                found = definition_list::parse(&lines[i ..]).map(|(list, n)| (Block::DefinitionList(list), n));
+            }
+
+            if let Some((block, consumed)) = found {
+                let index = first_block + source.blocks.len();
+
+                source.text.write_all(&placeholder_prefix(nonce)).unwrap();
+                write!(source.text, "{}", index).unwrap();
+                source.text.write_all(PLACEHOLDER_SUFFIX).unwrap();
+
+                for _ in 0 .. consumed {
+                    source.text.push(b'\n');
+                }
+
+                source.blocks.push(block);
+                i += consumed;
+                continue;
+            }
+        }
+
+        if extensions.contains(TASK_LISTS) && in_list {
+            let index = first_task + source.tasks.len();
+
+            if let Some((line, marker)) = task_list::rewrite(line, nonce, index) {
+                source.text.extend_from_slice(&line);
+                source.tasks.push(marker);
+                i += 1;
+                continue;
+            }
+        }
+
+        if extensions.contains(HEADER_ATTRIBUTES) {
+            let index = first_attributes + source.attributes.len();
+
+            if let Some((line, found)) = attributes::rewrite(line, lines.get(i + 1).cloned(), extensions, nonce, index) {
+                source.text.extend_from_slice(&line);
+                source.attributes.push(found);
+                i += 1;
+                continue;
+            }
+        }
+
+        source.text.extend_from_slice(line);
+        i += 1;
+    }
+
+    source
+}
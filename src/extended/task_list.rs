@@ -0,0 +1,142 @@
+//! Task list items
+//!
+//! A list item may begin with a task marker, e.g. `- [x] done`. The marker is
+//! replaced with a placeholder tag, which hoedown passes to `html_span`, so it
+//! ends up at the start of the item's rendered content where it's recognized
+//! and removed. Like block placeholders, it includes the render's nonce.
+
+use std::io::Write;
+use std::str;
+
+use buffer::Buffer;
+
+const PLACEHOLDER_SUFFIX: &[u8] = b">";
+
+fn placeholder_prefix(nonce: &str) -> Vec<u8> {
+    format!("<{}-task-", nonce).into_bytes()
+}
+
+/// The offset at which the content of the list item on the line begins, if
+/// the line begins a list item at any indentation
+fn item_content(line: &[u8]) -> Option<usize> {
+    let indent = line.iter().take_while(|&&c| c == b' ' || c == b'\t').count();
+    let digits = line[indent ..].iter().take_while(|&&c| c.is_ascii_digit()).count();
+
+    let marker = match line.get(indent) {
+        Some(&b'-') | Some(&b'*') | Some(&b'+') => 1,
+        _ if digits > 0 && line.get(indent + digits) == Some(&b'.') => digits + 1,
+        _ => return None,
+    };
+
+    let spaces = line[indent + marker ..].iter().take_while(|&&c| c == b' ' || c == b'\t').count();
+
+    if spaces > 0 { Some(indent + marker + spaces) } else { None }
+}
+
+/// Whether the line begins a list item at any indentation
+pub fn is_item(line: &[u8]) -> bool {
+    item_content(line).is_some()
+}
+
+/// Whether the task marker is checked
+pub fn is_checked(marker: &[u8]) -> bool {
+    marker.starts_with(b"[x]") || marker.starts_with(b"[X]")
+}
+
+/// Replace the task marker of the list item on the line with a placeholder
+///
+/// Returns the rewritten line along with the marker, including the whitespace
+/// after it.
+pub fn rewrite(line: &[u8], nonce: &str, index: usize) -> Option<(Vec<u8>, Vec<u8>)> {
+    let start = match item_content(line) {
+        Some(start) => start,
+        None => return None,
+    };
+
+    match line.get(start .. start + 3) {
+        Some(b"[ ]") | Some(b"[x]") | Some(b"[X]") => (),
+        _ => return None,
+    }
+
+    let end = match line.get(start + 3) {
+        Some(&b' ') | Some(&b'\t') => start + 4,
+        Some(&b'\r') | Some(&b'\n') | None => start + 3,
+        _ => return None,
+    };
+
+    let mut rewritten = line[.. start].to_vec();
+    rewritten.extend_from_slice(&placeholder_prefix(nonce));
+    write!(rewritten, "{}", index).unwrap();
+    rewritten.extend_from_slice(PLACEHOLDER_SUFFIX);
+    rewritten.extend_from_slice(&line[end ..]);
+
+    Some((rewritten, line[start .. end].to_vec()))
+}
+
+/// If the text begins with a task placeholder, the index of its marker and the
+/// placeholder's length
+pub fn index(text: &[u8], nonce: &str) -> Option<(usize, usize)> {
+    let prefix = placeholder_prefix(nonce);
+
+    if !text.starts_with(&prefix) {
+        return None;
+    }
+
+    let digits = text[prefix.len() ..].iter().take_while(|&&c| c.is_ascii_digit()).count();
+    let end = prefix.len() + digits;
+
+    if digits == 0 || !text[end ..].starts_with(PLACEHOLDER_SUFFIX) {
+        return None;
+    }
+
+    str::from_utf8(&text[prefix.len() .. end]).ok()
+        .and_then(|i| i.parse().ok())
+        .map(|index| (index, end + PLACEHOLDER_SUFFIX.len()))
+}
+
+/// Remove the first task placeholder from the rendered content of a list item,
+/// returning the index of its marker along with the remaining content
+pub fn strip(content: &[u8], nonce: &str) -> Option<(usize, Buffer)> {
+    let prefix = placeholder_prefix(nonce);
+
+    content.windows(prefix.len())
+        .position(|w| w == &prefix[..])
+        .and_then(|start| index(&content[start ..], nonce).map(|(index, len)| {
+            let mut stripped = Buffer::new(64);
+            stripped.write_all(&content[.. start]).unwrap();
+            stripped.write_all(&content[start + len ..]).unwrap();
+
+            (index, stripped)
+        }))
+}
+
+/// Put back the markers of any placeholders that didn't end up at the start of
+/// a list item, e.g. because hoedown continued a paragraph with the line
+pub fn restore(output: &[u8], nonce: &str, markers: &[Option<Vec<u8>>]) -> Option<Vec<u8>> {
+    let prefix = placeholder_prefix(nonce);
+
+    if !output.windows(prefix.len()).any(|w| w == &prefix[..]) {
+        return None;
+    }
+
+    let mut restored = Vec::with_capacity(output.len());
+    let mut pos = 0;
+
+    while let Some(start) = output[pos ..].windows(prefix.len()).position(|w| w == &prefix[..]).map(|i| pos + i) {
+        restored.extend_from_slice(&output[pos .. start]);
+
+        match index(&output[start ..], nonce) {
+            Some((index, len)) => {
+                markers.get(index).and_then(|m| m.as_ref()).map(|m| restored.extend_from_slice(m));
+                pos = start + len;
+            },
+            None => {
+                restored.extend_from_slice(&prefix);
+                pos = start + prefix.len();
+            },
+        }
+    }
+
+    restored.extend_from_slice(&output[pos ..]);
+    Some(restored)
+}
@@ -0,0 +1,195 @@
+//! Wiki link processing
+//!
+//! Wiki links such as `[[Page]]` and `[[Page|label]]` are rewritten into
+//! regular inline links to a placeholder url, e.g. `[label](hoedown-0-wiki:0)`,
+//! so that hoedown parses their labels as usual. The placeholder is then
+//! recognized when hoedown reports the link. Like other placeholders, it
+//! includes the render's nonce.
+//!
+//! Code isn't rewritten. Other text that isn't parsed for links, such as
+//! html, has the rewriting undone.
+
+use std::io::Write;
+
+use extensions::Extension;
+use super::source::{lines, code_lines};
+
+/// A wiki link that was found in the source
+pub struct WikiLink {
+    /// The page that is linked to
+    pub target: Vec<u8>,
+
+    /// The markdown of the wiki link as it was written
+    pub original: Vec<u8>,
+
+    /// The markdown of the regular link it was rewritten into
+    pub rewritten: Vec<u8>,
+}
+
+fn trim(text: &[u8]) -> &[u8] {
+    let start = text.iter().position(|&c| c != b' ' && c != b'\t').unwrap_or(text.len());
+    let end = text.iter().rposition(|&c| c != b' ' && c != b'\t').map_or(start, |i| i + 1);
+
+    &text[start .. end]
+}
+
+/// Parse the contents of a wiki link into its target and label
+fn parse(inner: &[u8]) -> Option<(&[u8], &[u8])> {
+    if inner.iter().any(|&c| c == b'[' || c == b']' || c == b'\n' || c == b'\r') {
+        return None;
+    }
+
+    let (target, label) = match inner.iter().position(|&c| c == b'|') {
+        Some(bar) => (trim(&inner[.. bar]), trim(&inner[bar + 1 ..])),
+        None => (trim(inner), trim(inner)),
+    };
+
+    if target.is_empty() || label.is_empty() {
+        None
+    } else {
+        Some((target, label))
+    }
+}
+
+fn scheme(nonce: &str) -> Vec<u8> {
+    format!("{}-wiki:", nonce).into_bytes()
+}
+
+/// The start of the run of backticks closing a code span opened by `run` backticks
+fn closing_backticks(text: &[u8], from: usize, run: usize) -> Option<usize> {
+    let mut pos = from;
+
+    while pos < text.len() {
+        if text[pos] == b'`' {
+            let len = text[pos ..].iter().take_while(|&&c| c == b'`').count();
+
+            if len == run {
+                return Some(pos);
+            }
+
+            pos += len;
+        } else {
+            pos += 1;
+        }
+    }
+
+    None
+}
+
+/// Rewrite the wiki links outside of code spans in text without code blocks
+fn rewrite_spans(text: &[u8], nonce: &str, first: usize, output: &mut Vec<u8>, links: &mut Vec<WikiLink>) {
+    let mut pos = 0;
+    let mut i = 0;
+
+    while i < text.len() {
+        match text[i] {
+            // an escaped character can't open a code span or a wiki link
+            b'\\' => i += 2,
+            b'`' => {
+                let run = text[i ..].iter().take_while(|&&c| c == b'`').count();
+                i = closing_backticks(text, i + run, run).map_or(i + run, |close| close + run);
+            },
+            b'[' if text[i ..].starts_with(b"[[") && (i == 0 || text[i - 1] != b'!') => {
+                let end = text[i + 2 ..].windows(2).position(|w| w == b"]]").map(|e| i + 2 + e);
+
+                match end.and_then(|end| parse(&text[i + 2 .. end]).map(|link| (end, link))) {
+                    Some((end, (target, label))) => {
+                        let mut rewritten = vec![b'['];
+                        rewritten.extend_from_slice(label);
+                        rewritten.extend_from_slice(b"](");
+                        rewritten.extend_from_slice(&scheme(nonce));
+                        write!(rewritten, "{})", first + links.len()).unwrap();
+
+                        output.extend_from_slice(&text[pos .. i]);
+                        output.extend_from_slice(&rewritten);
+
+                        links.push(WikiLink {
+                            target: target.to_vec(),
+                            original: text[i .. end + 2].to_vec(),
+                            rewritten: rewritten,
+                        });
+
+                        pos = end + 2;
+                        i = pos;
+                    },
+                    None => i += 1,
+                }
+            },
+            _ => i += 1,
+        }
+    }
+
+    output.extend_from_slice(&text[pos.min(text.len()) ..]);
+}
+
+/// Rewrite the wiki links in the text into placeholder links
+///
+/// The placeholders include the nonce and are numbered starting from `first`.
+/// Wiki links within code, or that are escaped with a backslash or preceded by
+/// `!`, are left alone. Code blocks are only recognized if `blocks` is set,
+/// i.e. if the text is a document rather than inline markdown.
+pub fn rewrite(text: &[u8], extensions: Extension, blocks: bool, nonce: &str, first: usize) -> (Vec<u8>, Vec<WikiLink>) {
+    let mut output = Vec::with_capacity(text.len());
+    let mut links = vec![];
+
+    let lines = lines(text);
+    let code = if blocks { code_lines(&lines, extensions) } else { vec![false; lines.len()] };
+
+    let mut i = 0;
+    let mut offset = 0;
+
+    // rewrite each run of lines outside of code blocks as a whole, since code spans may span lines
+    while i < lines.len() {
+        let start = offset;
+        let is_code = code[i];
+
+        while i < lines.len() && code[i] == is_code {
+            offset += lines[i].len();
+            i += 1;
+        }
+
+        if is_code {
+            output.extend_from_slice(&text[start .. offset]);
+        } else {
+            rewrite_spans(&text[start .. offset], nonce, first, &mut output, &mut links);
+        }
+    }
+
+    (output, links)
+}
+
+/// The index of the wiki link that a placeholder url refers to
+pub fn index(link: &[u8], nonce: &str) -> Option<usize> {
+    let scheme = scheme(nonce);
+
+    if !link.starts_with(&scheme) {
+        return None;
+    }
+
+    ::std::str::from_utf8(&link[scheme.len() ..]).ok().and_then(|i| i.parse().ok())
+}
+
+/// Undo the rewriting of any wiki links in the text
+///
+/// Returns `None` if the text doesn't contain any rewritten wiki links.
+pub fn restore(text: &[u8], nonce: &str, links: &[WikiLink]) -> Option<Vec<u8>> {
+    let scheme = scheme(nonce);
+
+    if !text.windows(scheme.len()).any(|w| w == &scheme[..]) {
+        return None;
+    }
+
+    let mut restored = text.to_vec();
+
+    for link in links {
+        let found = restored.windows(link.rewritten.len()).position(|w| w == &link.rewritten[..]);
+
+        if let Some(start) = found {
+            let rest = restored.split_off(start);
+            restored.extend_from_slice(&link.original);
+            restored.extend_from_slice(&rest[link.rewritten.len() ..]);
+        }
+    }
+
+    Some(restored)
+}
@@ -50,5 +50,29 @@ bitflags! {
 
         /// Ignore indented code blocks
         const DISABLE_INDENTED_CODE = 1 << 14,
+
+        // bindings-level: processed by these bindings rather than by hoedown
+
+        /// Process task list items, e.g. `- [x] done`
+        const TASK_LISTS            = 1 << 16,
+
+        /// Process definition lists, e.g. `Term` followed by `: Definition`
+        const DEFINITION_LISTS      = 1 << 17,
+
+        /// Process admonitions, e.g. `!!! note "Title"` or `> [!WARNING]`
+        const ADMONITIONS           = 1 << 18,
+
+        /// Process wiki links, e.g. `[[Page]]` or `[[Page|label]]`
+        const WIKI_LINKS            = 1 << 19,
+
+        /// Process header attribute blocks, e.g. `## Install {#install .important}`
+        const HEADER_ATTRIBUTES     = 1 << 20,
+
+        /// Report the source position of each top-level block
+        ///
+        /// Positions are only reported for top-level blocks, through the
+        /// `positioned_block` callback. Nested blocks, spans and the footnotes
+        /// section don't get positions.
+        const SOURCE_POSITIONS      = 1 << 21,
     }
 }
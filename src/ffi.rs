@@ -16,7 +16,7 @@ mod callbacks {
     pub type table_header = extern "C" fn(*mut hoedown_buffer, *const hoedown_buffer, *mut c_void) -> ();
     pub type table_body = extern "C" fn(*mut hoedown_buffer, *const hoedown_buffer, *mut c_void) -> ();
     pub type table_row = extern "C" fn(*mut hoedown_buffer, *const hoedown_buffer, *mut c_void) -> ();
-    pub type table_cell = extern "C" fn(*mut hoedown_buffer, *const hoedown_buffer, ::renderer::Table, *mut c_void) -> ();
+    pub type table_cell = extern "C" fn(*mut hoedown_buffer, *const hoedown_buffer, u32, *mut c_void) -> ();
     pub type footnotes = extern "C" fn(*mut hoedown_buffer, *const hoedown_buffer, *mut c_void) -> ();
     pub type footnote_def = extern "C" fn(*mut hoedown_buffer, *const hoedown_buffer, c_uint, *mut c_void) -> ();
     pub type blockhtml = extern "C" fn(*mut hoedown_buffer, *const hoedown_buffer, *mut c_void) -> ();
@@ -142,6 +142,63 @@ extern "C" {
 
     pub fn hoedown_html_renderer_free(renderer: *mut hoedown_renderer);
 
+    // escape
+    pub fn hoedown_escape_href(
+        output: *mut hoedown_buffer,
+        data: *const u8,
+        size: size_t,
+    );
+
+    pub fn hoedown_escape_html(
+        output: *mut hoedown_buffer,
+        data: *const u8,
+        size: size_t,
+        secure: c_int,
+    );
+
+    // version
+    pub fn hoedown_version(
+        major: *mut c_int,
+        minor: *mut c_int,
+        revision: *mut c_int,
+    );
+
+    // autolink
+    //
+    // The detectors take a mutable pointer to the text in C, but only ever
+    // read from it, so they're declared with a const pointer here.
+    pub fn hoedown_autolink_is_safe(
+        data: *const u8,
+        size: size_t,
+    ) -> c_int;
+
+    pub fn hoedown_autolink__www(
+        rewind: *mut size_t,
+        link: *mut hoedown_buffer,
+        data: *const u8,
+        max_rewind: size_t,
+        size: size_t,
+        flags: c_uint,
+    ) -> size_t;
+
+    pub fn hoedown_autolink__email(
+        rewind: *mut size_t,
+        link: *mut hoedown_buffer,
+        data: *const u8,
+        max_rewind: size_t,
+        size: size_t,
+        flags: c_uint,
+    ) -> size_t;
+
+    pub fn hoedown_autolink__url(
+        rewind: *mut size_t,
+        link: *mut hoedown_buffer,
+        data: *const u8,
+        max_rewind: size_t,
+        size: size_t,
+        flags: c_uint,
+    ) -> size_t;
+
     // document
     pub fn hoedown_document_new(
         renderer: *const hoedown_renderer,
@@ -0,0 +1,209 @@
+//! Front matter detection
+//!
+//! Many markdown documents begin with a block of metadata delimited by `---`
+//! (YAML) or `+++` (TOML) lines. Hoedown knows nothing about these blocks and
+//! would otherwise render them as a horizontal rule followed by a paragraph.
+//!
+//!``` rust
+//!# use hoedown::Markdown;
+//!# use hoedown::front_matter::Format;
+//!let (front, body) = Markdown::with_front_matter("---\ntitle: Test\n---\n# Header\n");
+//!let front = front.unwrap();
+//!
+//!assert_eq!(front.format, Format::Yaml);
+//!assert_eq!(front.contents.to_str().unwrap(), "title: Test\n");
+//!assert_eq!(front.body_offset, 20);
+//!assert_eq!(body.contents.to_str().unwrap(), "# Header\n");
+//!```
+
+use std::ops::Range;
+
+#[cfg(feature = "front-matter")]
+use std::{error, fmt, str};
+
+#[cfg(feature = "front-matter")]
+use serde::de::DeserializeOwned;
+
+use buffer::Buffer;
+
+/// The format of a front matter block
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// A block delimited by `---` lines
+    Yaml,
+
+    /// A block delimited by `+++` lines
+    Toml,
+}
+
+/// A metadata block found at the start of a document
+#[derive(Clone)]
+pub struct FrontMatter {
+    /// The format of the block, as determined by its delimiters
+    pub format: Format,
+
+    /// The raw metadata, without the delimiters
+    pub contents: Buffer,
+
+    /// The byte range of the raw metadata within the original document
+    pub range: Range<usize>,
+
+    /// The byte offset at which the markdown body begins within the original document
+    pub body_offset: usize,
+}
+
+impl FrontMatter {
+    /// Detect a front matter block at the very start of the given input
+    ///
+    /// The opening delimiter must be the first line of the input, and the
+    /// block must be terminated by a matching delimiter line. YAML blocks may
+    /// also be terminated by a `...` line.
+    pub fn detect(input: &[u8]) -> Option<FrontMatter> {
+        let (first, mut pos) = match next_line(input, 0) {
+            Some(line) => line,
+            None => return None,
+        };
+
+        let format = match trim_end(first) {
+            b"---" => Format::Yaml,
+            b"+++" => Format::Toml,
+            _ => return None,
+        };
+
+        let start = pos;
+
+        while let Some((line, next)) = next_line(input, pos) {
+            let closes = match (format, trim_end(line)) {
+                (Format::Yaml, b"---") | (Format::Yaml, b"...") => true,
+                (Format::Toml, b"+++") => true,
+                _ => false,
+            };
+
+            if closes {
+                return Some(FrontMatter {
+                    format: format,
+                    contents: Buffer::from(&input[start .. pos]),
+                    range: start .. pos,
+                    body_offset: next,
+                });
+            }
+
+            pos = next;
+        }
+
+        None
+    }
+
+    /// Find a top-level `title` key in the metadata, without deserializing it
+    ///
+    /// Only simple values are recognized, e.g. `title: Some Title` in YAML or
+    /// `title = "Some Title"` in TOML.
+    pub fn title(&self) -> Option<String> {
+        let separator = match self.format {
+            Format::Yaml => b':',
+            Format::Toml => b'=',
+        };
+
+        let mut pos = 0;
+
+        while let Some((line, next)) = next_line(&self.contents, pos) {
+            pos = next;
+
+            if !line.starts_with(b"title") {
+                continue;
+            }
+
+            let rest = trim_start(&line[5 ..]);
+
+            if rest.first() != Some(&separator) {
+                continue;
+            }
+
+            let value = trim_start(trim_end(&rest[1 ..]));
+
+            let value =
+                if value.len() >= 2 && (value[0] == b'"' || value[0] == b'\'') && value[value.len() - 1] == value[0] {
+                    &value[1 .. value.len() - 1]
+                } else {
+                    value
+                };
+
+            if value.is_empty() {
+                return None;
+            }
+
+            return Some(String::from_utf8_lossy(value).into_owned());
+        }
+
+        None
+    }
+}
+
+/// Errors that can occur while deserializing front matter
+#[cfg(feature = "front-matter")]
+#[derive(Debug)]
+pub enum Error {
+    /// The metadata isn't valid UTF-8
+    Utf8(str::Utf8Error),
+
+    /// The YAML metadata couldn't be deserialized
+    Yaml(::serde_yaml::Error),
+
+    /// The TOML metadata couldn't be deserialized
+    Toml(::toml::de::Error),
+}
+
+#[cfg(feature = "front-matter")]
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Utf8(ref e) => write!(f, "front matter is not valid utf-8: {}", e),
+            Error::Yaml(ref e) => write!(f, "invalid yaml front matter: {}", e),
+            Error::Toml(ref e) => write!(f, "invalid toml front matter: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "front-matter")]
+impl error::Error for Error {}
+
+#[cfg(feature = "front-matter")]
+impl FrontMatter {
+    /// Deserialize the metadata into the given type
+    ///
+    /// Only available with the `front-matter` feature.
+    pub fn deserialize<T>(&self) -> Result<T, Error>
+    where T: DeserializeOwned {
+        let contents = try!(self.contents.to_str().map_err(Error::Utf8));
+
+        match self.format {
+            Format::Yaml => ::serde_yaml::from_str(contents).map_err(Error::Yaml),
+            Format::Toml => ::toml::from_str(contents).map_err(Error::Toml),
+        }
+    }
+}
+
+/// Get the line beginning at `pos`, excluding its line ending, along with the
+/// position of the following line
+fn next_line(input: &[u8], pos: usize) -> Option<(&[u8], usize)> {
+    if pos >= input.len() {
+        return None;
+    }
+
+    let rest = &input[pos ..];
+
+    match rest.iter().position(|&b| b == b'\n') {
+        Some(end) => Some((&rest[.. end], pos + end + 1)),
+        None => Some((rest, input.len())),
+    }
+}
+
+fn trim_start(line: &[u8]) -> &[u8] {
+    let start = line.iter().position(|&b| b != b' ' && b != b'\t').unwrap_or(line.len());
+    &line[start ..]
+}
+
+fn trim_end(line: &[u8]) -> &[u8] {
+    let end = line.iter().rposition(|&b| b != b' ' && b != b'\t' && b != b'\r').map_or(0, |i| i + 1);
+    &line[.. end]
+}
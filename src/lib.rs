@@ -22,13 +22,34 @@ extern crate libc;
 #[macro_use]
 extern crate bitflags;
 
+#[cfg(feature = "front-matter")]
+extern crate serde;
+#[cfg(feature = "front-matter")]
+extern crate serde_yaml;
+#[cfg(feature = "front-matter")]
+extern crate toml;
+#[cfg(feature = "derive")]
+extern crate hoedown_derive;
+
 mod extensions;
 pub mod ffi;
 mod buffer;
+pub mod escape;
+#[macro_use]
 pub mod renderer;
 mod document;
 mod wrappers;
+mod extended;
 mod markdown;
+mod version;
+pub mod front_matter;
+pub mod emoji;
+pub mod smartypants;
+pub mod autolink;
+pub mod lint;
+pub mod links;
+pub mod tables;
+pub mod link_check;
 
 pub use extensions::*;
 
@@ -36,6 +57,11 @@ pub use buffer::Buffer;
 
 pub use renderer::Render;
 pub use markdown::Markdown;
+pub use version::{version, VERSION_MAJOR};
+pub use front_matter::FrontMatter;
 pub use renderer::html::Html;
 pub use renderer::wrapper::Wrapper;
 pub use renderer::trace::Trace;
+
+#[cfg(feature = "derive")]
+pub use hoedown_derive::{Render, overrides};
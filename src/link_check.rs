@@ -0,0 +1,253 @@
+//! Offline link checking
+//!
+//! Checks the links of a set of documents against the local filesystem:
+//! relative links must point to existing files, fragments must name an id in
+//! the target document's html, and footnote references must have definitions.
+//! Links with a scheme, such as `https:` or `mailto:`, aren't checked.
+//!
+//!``` rust,no_run
+//!# use hoedown::Markdown;
+//!# use hoedown::link_check::Checker;
+//!let mut checker = Checker::new("docs");
+//!checker.add_document("index.md", Markdown::new("See [install](install.md#setup)."));
+//!
+//!for broken in checker.check() {
+//!    println!("{}:{}: {}", broken.path.display(), broken.position.start_line, broken.url);
+//!}
+//!```
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use std::str;
+
+use links::Kind;
+use markdown::Markdown;
+use renderer::{Render, SourcePosition};
+use renderer::html::{self, Html};
+
+/// Why a link is broken
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reason {
+    /// The linked file doesn't exist
+    MissingFile,
+
+    /// The target document has no element with the fragment's id
+    MissingAnchor,
+
+    /// The footnote reference has no definition
+    MissingFootnote,
+
+    /// The link's path leads outside of the root directory
+    OutsideRoot,
+}
+
+/// A broken link found by the checker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenLink {
+    /// The document containing the link, relative to the root
+    pub path: PathBuf,
+
+    /// The link's url, or the label of a footnote reference
+    pub url: String,
+
+    pub reason: Reason,
+
+    /// The position of the link within the document
+    pub position: SourcePosition,
+}
+
+/// Checks the links of a set of documents within a root directory
+pub struct Checker {
+    root: PathBuf,
+    nesting_level: i32,
+    documents: Vec<(PathBuf, Markdown)>,
+}
+
+/// Remove `.` and `..` components without consulting the filesystem
+///
+/// Returns `None` if a `..` component leads outside of the root.
+fn normalize(path: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir | Component::RootDir | Component::Prefix(_) => (),
+            Component::ParentDir => if !normalized.pop() { return None },
+            Component::Normal(part) => normalized.push(part),
+        }
+    }
+
+    Some(normalized)
+}
+
+/// Decode percent-encoded bytes, leaving invalid sequences as they are
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escape =
+            if bytes[i] == b'%' && i + 2 < bytes.len() {
+                str::from_utf8(&bytes[i + 1 .. i + 3]).ok().and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            } else {
+                None
+            };
+
+        match escape {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            },
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            },
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Whether the url has a scheme, e.g. `https:`, or is protocol-relative
+fn is_external(url: &str) -> bool {
+    if url.starts_with("//") {
+        return true;
+    }
+
+    match url.find(':') {
+        Some(colon) => {
+            let scheme = &url[.. colon];
+
+            !scheme.is_empty() && scheme.chars().all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+        },
+        None => false,
+    }
+}
+
+/// The values of the `id` attributes in the html
+fn ids(html: &[u8]) -> HashSet<String> {
+    let mut ids = HashSet::new();
+    let attribute = b" id=\"";
+    let mut pos = 0;
+
+    while let Some(start) = html[pos ..].windows(attribute.len()).position(|w| w == attribute) {
+        let start = pos + start + attribute.len();
+        let end = html[start ..].iter().position(|&c| c == b'"').map_or(html.len(), |i| start + i);
+
+        ids.insert(String::from_utf8_lossy(&html[start .. end]).into_owned());
+        pos = end;
+    }
+
+    ids
+}
+
+impl Checker {
+    /// Construct a checker for documents within the given root directory
+    pub fn new<P>(root: P) -> Checker
+    where P: AsRef<Path> {
+        Checker {
+            root: root.as_ref().to_path_buf(),
+            nesting_level: 0,
+            documents: vec![],
+        }
+    }
+
+    /// Builder method to specify the table of contents nesting level that
+    /// documents are rendered with
+    ///
+    /// This determines which headers `Html` gives `toc_N` ids.
+    pub fn nesting_level(mut self, nesting_level: i32) -> Checker {
+        self.nesting_level = nesting_level;
+        self
+    }
+
+    /// Add a document to be checked, given its path relative to the root
+    pub fn add_document<P>(&mut self, path: P, doc: Markdown)
+    where P: AsRef<Path> {
+        let path = path.as_ref();
+        self.documents.push((normalize(path).unwrap_or_else(|| path.to_path_buf()), doc));
+    }
+
+    /// Check the links of every document
+    pub fn check(&self) -> Vec<BrokenLink> {
+        let anchors: HashMap<&Path, HashSet<String>> =
+            self.documents.iter()
+            .map(|&(ref path, ref doc)| {
+                let mut html = Html::new(html::Flags::empty(), self.nesting_level);
+                (path.as_path(), ids(&html.render(doc)))
+            })
+            .collect();
+
+        let mut broken = vec![];
+
+        for &(ref path, ref doc) in &self.documents {
+            let links = doc.links();
+            let report = |url: &str, reason: Reason, position: &SourcePosition| BrokenLink {
+                path: path.clone(),
+                url: String::from(url),
+                reason: reason,
+                position: position.clone(),
+            };
+
+            for link in &links.links {
+                if link.kind != Kind::Link && link.kind != Kind::Image {
+                    continue;
+                }
+
+                if let Some(reason) = self.check_url(path, &link.url, &anchors) {
+                    broken.push(report(&link.url, reason, &link.position));
+                }
+            }
+
+            let definitions: HashSet<&str> = links.footnote_definitions.iter().map(|d| &d.label[..]).collect();
+
+            for reference in &links.footnote_references {
+                if !definitions.contains(&reference.label[..]) {
+                    broken.push(report(&reference.label, Reason::MissingFootnote, &reference.position));
+                }
+            }
+        }
+
+        broken
+    }
+
+    fn check_url(&self, path: &Path, url: &str, anchors: &HashMap<&Path, HashSet<String>>) -> Option<Reason> {
+        if url.is_empty() || is_external(url) {
+            return None;
+        }
+
+        let (target, fragment) = match url.find('#') {
+            Some(hash) => (&url[.. hash], Some(&url[hash + 1 ..])),
+            None => (url, None),
+        };
+
+        let target = &target[.. target.find('?').unwrap_or(target.len())];
+
+        let target =
+            if target.is_empty() {
+                Some(path.to_path_buf())
+            } else if target.starts_with('/') {
+                normalize(Path::new(&percent_decode(target)))
+            } else {
+                let directory = path.parent().unwrap_or(Path::new(""));
+                normalize(&directory.join(percent_decode(target)))
+            };
+
+        let target = match target {
+            Some(target) => target,
+            None => return Some(Reason::OutsideRoot),
+        };
+
+        if !self.root.join(&target).exists() && !anchors.contains_key(target.as_path()) {
+            return Some(Reason::MissingFile);
+        }
+
+        match (fragment, anchors.get(target.as_path())) {
+            (Some(fragment), Some(ids)) if !fragment.is_empty() && !ids.contains(&percent_decode(fragment)) => {
+                Some(Reason::MissingAnchor)
+            },
+            _ => None,
+        }
+    }
+}
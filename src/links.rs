@@ -0,0 +1,338 @@
+//! Link extraction
+//!
+//! Collects every link, image and autolink in a document, along with its
+//! footnote references and definitions, without writing a `Render` impl.
+//!
+//!``` rust
+//!# use hoedown::Markdown;
+//!# use hoedown::links::Kind;
+//!let doc = Markdown::new("See [the docs](docs/index.md \"Docs\").\n");
+//!let links = doc.links();
+//!
+//!assert_eq!(links.links.len(), 1);
+//!assert_eq!(links.links[0].kind, Kind::Link);
+//!assert_eq!(links.links[0].url, "docs/index.md");
+//!assert_eq!(links.links[0].title, Some(String::from("Docs")));
+//!assert_eq!(links.links[0].text, "the docs");
+//!assert_eq!(links.links[0].position.start_column, 16);
+//!```
+
+use std::io::Write;
+use std::mem;
+
+use buffer::Buffer;
+use extensions::{Extension, FOOTNOTES, SOURCE_POSITIONS};
+use extended::source::{lines, is_blank, unindent};
+use extended::position::{offsets, position, literal_lines};
+use markdown::Markdown;
+use renderer::{Render, SourcePosition, AutoLink};
+
+/// The kind of a link
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Kind {
+    /// An inline or reference link, e.g. `[text](url)`
+    Link,
+
+    /// An image, e.g. `![alt](url)`
+    Image,
+
+    /// A url or email address that was linked automatically
+    AutoLink(AutoLink),
+
+    /// A wiki link, e.g. `[[target]]`, whose url is its target
+    Wiki,
+}
+
+/// A link found in a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    /// What kind of link this is
+    pub kind: Kind,
+
+    /// The destination, as written
+    pub url: String,
+
+    /// The title, if any, e.g. `Docs` in `[docs](index.md "Docs")`
+    pub title: Option<String>,
+
+    /// The link's text without any markup, or an image's alt text
+    pub text: String,
+
+    /// The position of the url within the source
+    ///
+    /// If the url doesn't appear verbatim within its block, as with reference
+    /// links, this is instead the position of the containing top-level block.
+    pub position: SourcePosition,
+}
+
+/// A footnote reference or definition, e.g. `[^note]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Footnote {
+    /// The label, without the brackets and caret
+    pub label: String,
+
+    /// The position of the bracketed label within the source
+    pub position: SourcePosition,
+}
+
+/// The links found in a document, each in the order they appear
+///
+/// Images within a link's text come before the link itself.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Links {
+    /// The links, images, autolinks and wiki links
+    pub links: Vec<Link>,
+
+    /// Only found if the `FOOTNOTES` extension is enabled
+    pub footnote_references: Vec<Footnote>,
+
+    /// Only found if the `FOOTNOTES` extension is enabled
+    pub footnote_definitions: Vec<Footnote>,
+}
+
+/// Extract the links from the document
+///
+/// Links are found by rendering the document. Footnotes are found by scanning
+/// its text, skipping code blocks, code spans and html blocks.
+pub fn extract(doc: &Markdown) -> Links {
+    let positioned = doc.clone().extensions(doc.extensions | SOURCE_POSITIONS);
+    let mut collector = Collector::new(&doc.contents);
+    collector.render(&positioned);
+
+    let mut links = Links::default();
+    links.links = collector.links;
+
+    if doc.extensions.contains(FOOTNOTES) {
+        footnotes(&doc.contents, doc.extensions, &mut links);
+    }
+
+    links
+}
+
+fn to_string(buffer: Option<&Buffer>) -> Option<String> {
+    buffer.map(|b| String::from_utf8_lossy(b).into_owned())
+}
+
+/// The length of the footnote label at the start of the text, including its brackets
+fn footnote_label(text: &[u8]) -> Option<usize> {
+    if !text.starts_with(b"[^") {
+        return None;
+    }
+
+    let label = text[2 ..].iter().take_while(|&&c| c != b']' && c != b'[' && !is_blank(&[c])).count();
+
+    if label > 0 && text.get(2 + label) == Some(&b']') {
+        Some(label + 3)
+    } else {
+        None
+    }
+}
+
+fn footnotes(input: &[u8], extensions: Extension, links: &mut Links) {
+    let lines = lines(input);
+    let offsets = offsets(&lines);
+    let literal = literal_lines(input, extensions);
+
+    for (i, line) in lines.iter().enumerate() {
+        if literal[i] {
+            continue;
+        }
+
+        let footnote = |start: usize, len: usize| Footnote {
+            label: String::from_utf8_lossy(&line[start + 2 .. start + len - 1]).into_owned(),
+            position: position(&offsets, offsets[i] + start .. offsets[i] + start + len),
+        };
+
+        let mut pos = line.len() - unindent(line, 3).len();
+
+        if let Some(len) = footnote_label(&line[pos ..]) {
+            if line.get(pos + len) == Some(&b':') {
+                links.footnote_definitions.push(footnote(pos, len));
+                pos += len + 1;
+            }
+        }
+
+        while pos < line.len() {
+            match line[pos] {
+                b'\\' => pos += 2,
+                b'`' => {
+                    // skip the code span, if it's closed on this line
+                    let ticks = line[pos ..].iter().take_while(|&&c| c == b'`').count();
+                    let closing = line[pos + ticks ..].windows(ticks)
+                        .position(|w| w.iter().all(|&c| c == b'`'))
+                        .map(|end| pos + ticks + end + ticks);
+
+                    pos = closing.unwrap_or(pos + ticks);
+                },
+                _ => match footnote_label(&line[pos ..]) {
+                    Some(len) => {
+                        links.footnote_references.push(footnote(pos, len));
+                        pos += len;
+                    },
+                    None => pos += 1,
+                },
+            }
+        }
+    }
+}
+
+/// Renderer that collects the links in each top-level block
+///
+/// Spans are rendered as their bare content, so that link text is plain.
+struct Collector<'a> {
+    source: &'a [u8],
+
+    /// The offsets at which each of the source's lines begins
+    offsets: Vec<usize>,
+
+    /// Links found in the block being rendered
+    pending: Vec<Link>,
+
+    links: Vec<Link>,
+}
+
+impl<'a> Collector<'a> {
+    fn new(source: &'a [u8]) -> Collector<'a> {
+        Collector {
+            source: source,
+            offsets: offsets(&lines(source)),
+            pending: vec![],
+            links: vec![],
+        }
+    }
+
+    /// Collect a link
+    ///
+    /// Its position is filled in once its block has been rendered.
+    fn push(&mut self, kind: Kind, url: Option<&Buffer>, title: Option<&Buffer>, text: String) {
+        let title = to_string(title).and_then(|t| if t.is_empty() { None } else { Some(t) });
+
+        self.pending.push(Link {
+            kind: kind,
+            url: to_string(url).unwrap_or_default(),
+            title: title,
+            text: text,
+            position: position(&[0], 0 .. 0),
+        });
+    }
+
+    /// Fill in the positions of the pending links, which were found in the
+    /// given block
+    fn flush(&mut self, block: &SourcePosition) {
+        let source = self.source;
+        let mut cursor = block.range.start;
+
+        for mut link in mem::replace(&mut self.pending, vec![]) {
+            let url = link.url.as_bytes();
+            let found =
+                if url.is_empty() {
+                    None
+                } else {
+                    source[cursor .. block.range.end].windows(url.len())
+                        .position(|w| w == url)
+                        .map(|start| cursor + start)
+                };
+
+            link.position = match found {
+                Some(start) => {
+                    cursor = start + url.len();
+                    position(&self.offsets, start .. cursor)
+                },
+                None => block.clone(),
+            };
+
+            self.links.push(link);
+        }
+    }
+
+    fn span(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        content.map(|c| ob.pipe(c));
+        true
+    }
+}
+
+impl<'a> Render for Collector<'a> {
+    fn positioned_block(&mut self, _output: &mut Buffer, _content: Option<&Buffer>, block: &SourcePosition) {
+        self.flush(block);
+    }
+
+    fn autolink(&mut self, ob: &mut Buffer, link: Option<&Buffer>, link_type: AutoLink) -> bool {
+        let text = to_string(link).unwrap_or_default();
+        self.push(Kind::AutoLink(link_type), link, None, text);
+
+        link.map(|l| ob.pipe(l));
+        true
+    }
+
+    fn code_span(&mut self, ob: &mut Buffer, text: Option<&Buffer>) -> bool {
+        text.map(|t| ob.pipe(t));
+        true
+    }
+
+    fn double_emphasis(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn emphasis(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn underline(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn highlight(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn quote_span(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn image(&mut self, ob: &mut Buffer, link: Option<&Buffer>, title: Option<&Buffer>, alt: Option<&Buffer>) -> bool {
+        let text = to_string(alt).unwrap_or_default();
+        self.push(Kind::Image, link, title, text);
+
+        alt.map(|a| ob.pipe(a));
+        true
+    }
+
+    fn line_break(&mut self, ob: &mut Buffer) -> bool {
+        ob.write_all(b"\n").unwrap();
+        true
+    }
+
+    fn link(&mut self, ob: &mut Buffer, content: Option<&Buffer>, link: Option<&Buffer>, title: Option<&Buffer>) -> bool {
+        let text = to_string(content).unwrap_or_default();
+        self.push(Kind::Link, link, title, text);
+
+        content.map(|c| ob.pipe(c));
+        true
+    }
+
+    fn wiki_link(&mut self, ob: &mut Buffer, target: &Buffer, label: Option<&Buffer>) -> bool {
+        let text = to_string(label.or(Some(target))).unwrap_or_default();
+        self.push(Kind::Wiki, Some(target), None, text);
+
+        ob.pipe(label.unwrap_or(target));
+        true
+    }
+
+    fn triple_emphasis(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn strikethrough(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn superscript(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn after_render(&mut self, _output: &mut Buffer, _inline_render: bool) {
+        // e.g. the links in footnotes, which are rendered after the last block
+        let document = position(&self.offsets, 0 .. self.source.len());
+        self.flush(&document);
+    }
+}
@@ -0,0 +1,445 @@
+//! Markdown linting
+//!
+//! Checks a document against a set of rules using the same parser that renders
+//! it. Most rules are checked by rendering the document with a renderer that
+//! collects the elements it's given, so their diagnostics carry the position of
+//! the top-level block containing the problem, or of the whole document for
+//! problems within footnotes. Rules about the text itself, such as line
+//! lengths, carry the position of the offending line.
+//!
+//!``` rust
+//!# use hoedown::Markdown;
+//!# use hoedown::lint::{self, Linter};
+//!let doc = Markdown::new("# Title\n\n### Details\n");
+//!let diagnostics = Linter::new(lint::Rules::all()).lint(&doc);
+//!
+//!assert_eq!(diagnostics.len(), 1);
+//!assert_eq!(diagnostics[0].rule, lint::HEADER_INCREMENT);
+//!assert_eq!(diagnostics[0].position.start_line, 3);
+//!```
+
+use std::collections::HashSet;
+use std::io::Write;
+use std::mem;
+use std::str;
+
+use buffer::Buffer;
+use extensions::{AUTOLINK, FENCED_CODE, SOURCE_POSITIONS};
+use extended::source::{lines, is_blank, trim_end, unindent, fence, code_lines, nonce};
+use extended::position::{offsets, position};
+use markdown::Markdown;
+use renderer::{Render, SourcePosition, AutoLink};
+
+bitflags! {
+    /// Constants for the lint rules
+    pub flags Rules: u32 {
+        /// Header levels should only increase one at a time, e.g. no `###` directly after `#`
+        const HEADER_INCREMENT  = 1 << 0,
+
+        /// A document should have at most one top-level header
+        const SINGLE_TITLE      = 1 << 1,
+
+        /// Links should have both text and a destination
+        const EMPTY_LINKS       = 1 << 2,
+
+        /// Images should have alt text
+        const IMAGE_ALT_TEXT    = 1 << 3,
+
+        /// Headers should have distinct text
+        const DUPLICATE_HEADERS = 1 << 4,
+
+        /// URLs should be links, unless the `AUTOLINK` extension makes them so
+        const BARE_URLS         = 1 << 5,
+
+        /// Code fences should specify a language
+        const FENCE_LANGUAGE    = 1 << 6,
+
+        /// Lines shouldn't exceed the maximum line length
+        const LINE_LENGTH       = 1 << 7,
+    }
+}
+
+/// A problem found in a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The rule that was broken
+    pub rule: Rules,
+
+    /// A description of the problem
+    pub message: String,
+
+    /// Where the problem was found
+    pub position: SourcePosition,
+}
+
+/// Checks documents against a set of rules
+#[derive(Debug, Clone)]
+pub struct Linter {
+    rules: Rules,
+    max_line_length: usize,
+}
+
+impl Linter {
+    /// Construct a linter that checks the given rules
+    ///
+    /// The maximum line length defaults to 80 characters.
+    pub fn new(rules: Rules) -> Linter {
+        Linter {
+            rules: rules,
+            max_line_length: 80,
+        }
+    }
+
+    /// Builder method to specify the maximum line length, in characters
+    pub fn max_line_length(mut self, length: usize) -> Linter {
+        self.max_line_length = length;
+        self
+    }
+
+    /// Check the document, returning its diagnostics in the order they appear
+    pub fn lint(&self, doc: &Markdown) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+
+        self.check_elements(doc, &mut diagnostics);
+        self.check_lines(doc, &mut diagnostics);
+
+        diagnostics.retain(|d| self.rules.contains(d.rule));
+        diagnostics.sort_by_key(|d| (d.position.range.start, d.rule.bits()));
+        diagnostics
+    }
+
+    fn check_elements(&self, doc: &Markdown, diagnostics: &mut Vec<Diagnostic>) {
+        let doc = doc.clone().extensions(doc.extensions | SOURCE_POSITIONS);
+        let document = position(&offsets(&lines(&doc.contents)), 0 .. doc.contents.len());
+        let mut collector = Collector::new(doc.extensions.contains(AUTOLINK), nonce(&doc.contents), document);
+        collector.render(&doc);
+
+        let mut previous = None;
+        let mut titles = 0;
+        let mut headers = HashSet::new();
+
+        for (element, position) in collector.elements {
+            let (rule, message) = match element {
+                Element::Header { level, text } => {
+                    if previous.map_or(false, |previous| level > previous + 1) {
+                        diagnostics.push(Diagnostic {
+                            rule: HEADER_INCREMENT,
+                            message: format!("header level skips from h{} to h{}", previous.unwrap(), level),
+                            position: position.clone(),
+                        });
+                    }
+
+                    if level == 1 {
+                        titles += 1;
+
+                        if titles > 1 {
+                            diagnostics.push(Diagnostic {
+                                rule: SINGLE_TITLE,
+                                message: String::from("multiple top-level headers"),
+                                position: position.clone(),
+                            });
+                        }
+                    }
+
+                    previous = Some(level);
+
+                    if headers.insert(text.clone()) {
+                        continue;
+                    }
+
+                    (DUPLICATE_HEADERS, format!("duplicate header `{}`", text))
+                },
+                Element::EmptyLink => (EMPTY_LINKS, String::from("link without text or destination")),
+                Element::MissingAlt => (IMAGE_ALT_TEXT, String::from("image without alt text")),
+                Element::BareUrl { url, .. } => (BARE_URLS, format!("bare url `{}`", url)),
+            };
+
+            diagnostics.push(Diagnostic {
+                rule: rule,
+                message: message,
+                position: position,
+            });
+        }
+    }
+
+    fn check_lines(&self, doc: &Markdown, diagnostics: &mut Vec<Diagnostic>) {
+        let lines = lines(&doc.contents);
+        let offsets = offsets(&lines);
+        let code = code_lines(&lines, doc.extensions);
+        let mut fenced: Option<(u8, usize)> = None;
+
+        for (i, line) in lines.iter().enumerate() {
+            let text = trim_end(line);
+
+            if doc.extensions.contains(FENCED_CODE) {
+                match (fenced, fence(line)) {
+                    (Some((c, len)), Some((d, l))) => {
+                        if d == c && l >= len && is_blank(&unindent(line, 3)[l ..]) {
+                            fenced = None;
+                        }
+                    },
+                    (None, Some((c, len))) => {
+                        fenced = Some((c, len));
+
+                        if is_blank(&unindent(line, 3)[len ..]) {
+                            diagnostics.push(Diagnostic {
+                                rule: FENCE_LANGUAGE,
+                                message: String::from("code fence without a language"),
+                                position: position(&offsets, offsets[i] .. offsets[i] + text.len()),
+                            });
+                        }
+                    },
+                    _ => (),
+                }
+            }
+
+            // code can't be wrapped
+            if code[i] {
+                continue;
+            }
+
+            let length = str::from_utf8(text).map(|s| s.chars().count()).unwrap_or(text.len());
+
+            if length > self.max_line_length {
+                // the byte offset of the first character past the limit
+                let limit = str::from_utf8(text).ok()
+                    .and_then(|s| s.char_indices().nth(self.max_line_length).map(|(i, _)| i))
+                    .unwrap_or(self.max_line_length);
+
+                diagnostics.push(Diagnostic {
+                    rule: LINE_LENGTH,
+                    message: format!("line is {} characters long, exceeding {}", length, self.max_line_length),
+                    position: position(&offsets, offsets[i] + limit .. offsets[i] + text.len()),
+                });
+            }
+        }
+    }
+}
+
+/// An element of interest to the rules
+enum Element {
+    Header { level: i32, text: String },
+    EmptyLink,
+    MissingAlt,
+
+    /// A url found in text, along with the number of its marker
+    BareUrl { url: String, id: usize },
+}
+
+/// The urls within the text, which must start at a word boundary
+fn urls(text: &[u8]) -> Vec<String> {
+    let mut urls = vec![];
+    let mut pos = 0;
+
+    while pos < text.len() {
+        let rest = &text[pos ..];
+        let boundary = pos == 0 || !text[pos - 1].is_ascii_alphanumeric();
+
+        if boundary && (rest.starts_with(b"http://") || rest.starts_with(b"https://") || rest.starts_with(b"www.")) {
+            let end = rest.iter().position(|&c| is_blank(&[c]) || c == b'<' || c == b'>').unwrap_or(rest.len());
+            let url = rest[.. end].iter().rposition(|&c| !b".,;:!?)\"'".contains(&c)).map_or(0, |i| i + 1);
+
+            urls.push(String::from_utf8_lossy(&rest[.. url]).into_owned());
+            pos += end;
+        } else {
+            pos += 1;
+        }
+    }
+
+    urls
+}
+
+/// Renderer that collects the elements of interest in each top-level block
+///
+/// Spans are rendered as their bare content, so that header text is plain.
+///
+/// The text of a link is rendered before the link itself, so a marker is
+/// rendered along with each url found in text. The urls whose markers are
+/// within a link's text aren't bare.
+struct Collector {
+    autolink: bool,
+
+    /// The nonce included in markers, which doesn't occur in the document
+    nonce: String,
+
+    /// The number of urls found in text so far
+    urls: usize,
+
+    /// Elements found in the block being rendered
+    pending: Vec<Element>,
+
+    /// The position of the whole document, for elements rendered after the
+    /// last block, such as those in footnotes
+    document: SourcePosition,
+
+    /// Elements along with the position of the block they were found in
+    elements: Vec<(Element, SourcePosition)>,
+}
+
+impl Collector {
+    fn new(autolink: bool, nonce: String, document: SourcePosition) -> Collector {
+        Collector {
+            autolink: autolink,
+            nonce: nonce,
+            urls: 0,
+            pending: vec![],
+            document: document,
+            elements: vec![],
+        }
+    }
+
+    /// Give the pending elements the position of the block they were found in
+    fn flush(&mut self, position: &SourcePosition) {
+        let pending = mem::replace(&mut self.pending, vec![]);
+        self.elements.extend(pending.into_iter().map(|element| (element, position.clone())));
+    }
+
+    fn marker_prefix(&self) -> Vec<u8> {
+        format!("<{}-url:", self.nonce).into_bytes()
+    }
+
+    /// Split the rendered content into the numbers of the markers within it
+    /// and the content without them
+    fn markers(&self, content: &[u8]) -> (Vec<usize>, Vec<u8>) {
+        let prefix = self.marker_prefix();
+        let mut ids = vec![];
+        let mut text = Vec::with_capacity(content.len());
+        let mut pos = 0;
+
+        while let Some(start) = content[pos ..].windows(prefix.len()).position(|w| w == &prefix[..]).map(|i| pos + i) {
+            let digits = &content[start + prefix.len() ..];
+            let len = digits.iter().take_while(|c| c.is_ascii_digit()).count();
+
+            text.extend_from_slice(&content[pos .. start]);
+            pos = start + prefix.len();
+
+            if digits.get(len) == Some(&b'>') {
+                ids.extend(str::from_utf8(&digits[.. len]).ok().and_then(|id| id.parse::<usize>().ok()));
+                pos += len + 1;
+            }
+        }
+
+        text.extend_from_slice(&content[pos ..]);
+
+        (ids, text)
+    }
+
+    /// Render a span as its content
+    fn span(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        content.map(|c| ob.pipe(c));
+        true
+    }
+}
+
+impl Render for Collector {
+    fn header(&mut self, _ob: &mut Buffer, content: Option<&Buffer>, level: i32) {
+        let text = content.map_or(String::new(), |c| {
+            let (_, text) = self.markers(c);
+            String::from_utf8_lossy(trim_end(&text)).into_owned()
+        });
+
+        self.pending.push(Element::Header { level: level, text: text });
+    }
+
+    fn positioned_block(&mut self, _output: &mut Buffer, _content: Option<&Buffer>, position: &SourcePosition) {
+        self.flush(position);
+    }
+
+    fn autolink(&mut self, ob: &mut Buffer, link: Option<&Buffer>, _link_type: AutoLink) -> bool {
+        link.map(|l| ob.pipe(l));
+        true
+    }
+
+    fn code_span(&mut self, ob: &mut Buffer, text: Option<&Buffer>) -> bool {
+        text.map(|t| ob.pipe(t));
+        true
+    }
+
+    fn double_emphasis(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn emphasis(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn underline(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn highlight(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn quote_span(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn image(&mut self, _ob: &mut Buffer, _link: Option<&Buffer>, _title: Option<&Buffer>, alt: Option<&Buffer>) -> bool {
+        if alt.map_or(true, |alt| is_blank(alt)) {
+            self.pending.push(Element::MissingAlt);
+        }
+
+        true
+    }
+
+    fn line_break(&mut self, ob: &mut Buffer) -> bool {
+        ob.write_all(b"\n").unwrap();
+        true
+    }
+
+    fn link(&mut self, ob: &mut Buffer, content: Option<&Buffer>, link: Option<&Buffer>, _title: Option<&Buffer>) -> bool {
+        let (ids, text) = content.map_or((vec![], vec![]), |c| self.markers(c));
+
+        // urls in the link's text aren't bare
+        self.pending.retain(|element| match *element {
+            Element::BareUrl { id, .. } => !ids.contains(&id),
+            _ => true,
+        });
+
+        if is_blank(&text) || link.map_or(true, |l| is_blank(l)) {
+            self.pending.push(Element::EmptyLink);
+        }
+
+        ob.write_all(&text).unwrap();
+        true
+    }
+
+    fn triple_emphasis(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn strikethrough(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn superscript(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn footnote_reference(&mut self, _ob: &mut Buffer, _num: u32) -> bool {
+        true
+    }
+
+    fn normal_text(&mut self, ob: &mut Buffer, text: Option<&Buffer>) {
+        if let Some(text) = text {
+            if !self.autolink {
+                for url in urls(text) {
+                    let id = self.urls;
+                    self.urls += 1;
+
+                    ob.write_all(&self.marker_prefix()).unwrap();
+                    write!(ob, "{}>", id).unwrap();
+                    self.pending.push(Element::BareUrl { url: url, id: id });
+                }
+            }
+
+            ob.pipe(text);
+        }
+    }
+
+    fn after_render(&mut self, _output: &mut Buffer, _inline_render: bool) {
+        let document = self.document.clone();
+        self.flush(&document);
+    }
+}
@@ -2,6 +2,9 @@ use std::io::Read;
 
 use buffer::Buffer;
 use extensions::Extension;
+use front_matter::FrontMatter;
+use links::{self, Links};
+use tables::{self, Table};
 
 /// Markdown document
 #[derive(Clone)]
@@ -33,6 +36,48 @@ impl Markdown {
         }
     }
 
+    /// Construct a markdown document from a body that may begin with front matter
+    ///
+    /// Returns the front matter block, if any, along with a document consisting
+    /// of the remaining body. See `split_front_matter`.
+    pub fn with_front_matter(body: &str) -> (Option<FrontMatter>, Markdown) {
+        Markdown::new(body).split_front_matter()
+    }
+
+    /// Separate a leading front matter block from the rest of the document
+    ///
+    /// The returned document retains this document's extensions and maximum
+    /// nesting. The `body_offset` of the front matter can be used to map byte
+    /// offsets within the returned document back to the original input.
+    pub fn split_front_matter(self) -> (Option<FrontMatter>, Markdown) {
+        match FrontMatter::detect(&self.contents) {
+            Some(front) => {
+                let body = Markdown {
+                    contents: Buffer::from(&self.contents[front.body_offset ..]),
+                    extensions: self.extensions,
+                    max_nesting: self.max_nesting,
+                };
+
+                (Some(front), body)
+            },
+            None => (None, self),
+        }
+    }
+
+    /// Extract the document's links, images, autolinks and footnotes
+    ///
+    /// See the `links` module.
+    pub fn links(&self) -> Links {
+        links::extract(self)
+    }
+
+    /// Extract the document's tables, with cells as plain text
+    ///
+    /// See the `tables` module.
+    pub fn tables(&self) -> Vec<Table> {
+        tables::extract(self)
+    }
+
     /// Builder method to specify Hoedown extensions
     pub fn extensions(mut self, extensions: Extension) -> Markdown {
         self.extensions = extensions;
@@ -0,0 +1,199 @@
+//! Renderers built from closures
+//!
+//! Overriding a single callback of a renderer otherwise requires a `Wrapper`
+//! type. A `RendererBuilder` instead registers a closure for each callback to
+//! override, and builds a renderer which calls the base renderer for the rest.
+//!
+//!``` rust
+//!# use hoedown::{Markdown, Render, Html};
+//!# use hoedown::renderer::html::Flags;
+//!# use hoedown::renderer::builder::RendererBuilder;
+//!# use std::io::Write;
+//!let doc = Markdown::new("[home](/) and *more*\n");
+//!let mut renderer =
+//!    RendererBuilder::new(Html::new(Flags::empty(), 0))
+//!    .on_link(|output, content, _link, _title| {
+//!        output.write_all(b"[").unwrap();
+//!        content.map(|c| output.pipe(c));
+//!        output.write_all(b"]").unwrap();
+//!        true
+//!    })
+//!    .build();
+//!
+//!assert_eq!(renderer.render(&doc).to_str().unwrap(), "<p>[home] and <em>more</em></p>\n");
+//!```
+
+use buffer::Buffer;
+use super::{Render, Attributes, SourcePosition, AutoLink, Table, list};
+use super::wrapper::Wrapper;
+
+/// Define the renderer with a closure for each callback and its builder methods
+macro_rules! closures {
+    ($($(#[$doc:meta])* fn $name:ident, $on:ident($($arg:ident: $ty:ty),*) $(-> $ret:ty)*;)+) => {
+        /// Renderer which calls the closure registered for a callback, if
+        /// any, or else its base renderer
+        ///
+        /// This is constructed with a `RendererBuilder`.
+        pub struct Custom<R> {
+            base: R,
+            $($name: Option<Box<dyn FnMut(&mut Buffer, $($ty),*) $(-> $ret)*>>,)+
+        }
+
+        impl<R> RendererBuilder<R> where R: Render {
+            /// Construct a builder for a renderer which renders with the base
+            /// renderer by default
+            pub fn new(base: R) -> RendererBuilder<R> {
+                RendererBuilder {
+                    renderer: Custom {
+                        base: base,
+                        $($name: None,)+
+                    },
+                }
+            }
+
+            $(
+                $(#[$doc])*
+                pub fn $on<F>(mut self, callback: F) -> RendererBuilder<R>
+                where F: FnMut(&mut Buffer, $($ty),*) $(-> $ret)* + 'static {
+                    self.renderer.$name = Some(Box::new(callback));
+                    self
+                }
+            )+
+        }
+
+        impl<R> Wrapper for Custom<R> where R: Render {
+            type Base = R;
+
+            #[inline(always)]
+            fn base(&mut self) -> &mut R {
+                &mut self.base
+            }
+
+            $(
+                fn $name(&mut self, output: &mut Buffer, $($arg: $ty),*) $(-> $ret)* {
+                    match self.$name {
+                        Some(ref mut callback) => callback(output, $($arg),*),
+                        None => self.base.$name(output, $($arg),*),
+                    }
+                }
+            )+
+        }
+    }
+}
+
+/// Builder of a renderer from closures
+///
+/// Each closure takes the same arguments as the `Render` method of the same
+/// name, and returns the same value. Callbacks without a closure are rendered
+/// by the base renderer.
+pub struct RendererBuilder<R> {
+    renderer: Custom<R>,
+}
+
+impl<R> RendererBuilder<R> where R: Render {
+    /// Build the renderer
+    pub fn build(self) -> Custom<R> {
+        self.renderer
+    }
+}
+
+impl<R> Custom<R> where R: Render {
+    /// Unwrap the base renderer
+    pub fn into_inner(self) -> R {
+        self.base
+    }
+}
+
+wrap!([R: Render] Custom<R>);
+
+closures! {
+    /// Builder method to render code blocks
+    fn code_block, on_code_block(code: Option<&Buffer>, lang: Option<&Buffer>);
+    /// Builder method to render block quotes
+    fn quote_block, on_quote_block(content: Option<&Buffer>);
+    /// Builder method to render headers
+    fn header, on_header(content: Option<&Buffer>, level: i32);
+    /// Builder method to render headers with attributes
+    fn header_with_attributes, on_header_with_attributes(content: Option<&Buffer>, level: i32, attributes: &Attributes);
+    /// Builder method to render horizontal rules
+    fn horizontal_rule, on_horizontal_rule();
+    /// Builder method to render lists
+    fn list, on_list(content: Option<&Buffer>, flags: list::List);
+    /// Builder method to render list items
+    fn list_item, on_list_item(content: Option<&Buffer>, flags: list::List);
+    /// Builder method to render paragraphs
+    fn paragraph, on_paragraph(content: Option<&Buffer>);
+    /// Builder method to render tables
+    fn table, on_table(content: Option<&Buffer>);
+    /// Builder method to render table headers
+    fn table_header, on_table_header(content: Option<&Buffer>);
+    /// Builder method to render table bodies
+    fn table_body, on_table_body(content: Option<&Buffer>);
+    /// Builder method to render table rows
+    fn table_row, on_table_row(content: Option<&Buffer>, columns: usize);
+    /// Builder method to render table cells
+    fn table_cell, on_table_cell(content: Option<&Buffer>, flags: Table, column: usize, columns: usize);
+    /// Builder method to render the footnotes section
+    fn footnotes, on_footnotes(content: Option<&Buffer>);
+    /// Builder method to render footnote definitions
+    fn footnote_definition, on_footnote_definition(content: Option<&Buffer>, num: u32);
+    /// Builder method to render html blocks
+    fn html_block, on_html_block(text: Option<&Buffer>);
+    /// Builder method to render positioned blocks
+    fn positioned_block, on_positioned_block(content: Option<&Buffer>, position: &SourcePosition);
+    /// Builder method to render definition lists
+    fn definition_list, on_definition_list(content: Option<&Buffer>);
+    /// Builder method to render definition terms
+    fn definition_term, on_definition_term(content: Option<&Buffer>);
+    /// Builder method to render definition data
+    fn definition_data, on_definition_data(content: Option<&Buffer>);
+    /// Builder method to render admonitions
+    fn admonition, on_admonition(content: Option<&Buffer>, kind: &Buffer, title: Option<&Buffer>);
+
+    /// Builder method to render autolinks
+    fn autolink, on_autolink(link: Option<&Buffer>, link_type: AutoLink) -> bool;
+    /// Builder method to render code spans
+    fn code_span, on_code_span(text: Option<&Buffer>) -> bool;
+    /// Builder method to render double emphasis
+    fn double_emphasis, on_double_emphasis(content: Option<&Buffer>) -> bool;
+    /// Builder method to render emphasis
+    fn emphasis, on_emphasis(content: Option<&Buffer>) -> bool;
+    /// Builder method to render underlines
+    fn underline, on_underline(content: Option<&Buffer>) -> bool;
+    /// Builder method to render highlights
+    fn highlight, on_highlight(content: Option<&Buffer>) -> bool;
+    /// Builder method to render quote spans
+    fn quote_span, on_quote_span(content: Option<&Buffer>) -> bool;
+    /// Builder method to render images
+    fn image, on_image(link: Option<&Buffer>, title: Option<&Buffer>, alt: Option<&Buffer>) -> bool;
+    /// Builder method to render line breaks
+    fn line_break, on_line_break() -> bool;
+    /// Builder method to render links
+    fn link, on_link(content: Option<&Buffer>, link: Option<&Buffer>, title: Option<&Buffer>) -> bool;
+    /// Builder method to render wiki links
+    fn wiki_link, on_wiki_link(target: &Buffer, label: Option<&Buffer>) -> bool;
+    /// Builder method to render pattern links
+    fn pattern_link, on_pattern_link(text: &Buffer, link: &Buffer, name: &str) -> bool;
+    /// Builder method to render triple emphasis
+    fn triple_emphasis, on_triple_emphasis(content: Option<&Buffer>) -> bool;
+    /// Builder method to render strikethroughs
+    fn strikethrough, on_strikethrough(content: Option<&Buffer>) -> bool;
+    /// Builder method to render superscripts
+    fn superscript, on_superscript(content: Option<&Buffer>) -> bool;
+    /// Builder method to render footnote references
+    fn footnote_reference, on_footnote_reference(num: u32) -> bool;
+    /// Builder method to render math
+    fn math, on_math(text: Option<&Buffer>, displaymode: i32) -> bool;
+    /// Builder method to render html spans
+    fn html_span, on_html_span(text: Option<&Buffer>) -> bool;
+
+    /// Builder method to render entities
+    fn entity, on_entity(text: Option<&Buffer>);
+    /// Builder method to render normal text
+    fn normal_text, on_normal_text(text: Option<&Buffer>);
+
+    /// Builder method to run before rendering
+    fn before_render, on_before_render(inline_render: bool);
+    /// Builder method to run after rendering
+    fn after_render, on_after_render(inline_render: bool);
+}
@@ -0,0 +1,378 @@
+//! Dynamically dispatched renderers
+//!
+//! `Render` can't be made into a trait object, since it requires `Sized` for
+//! its rendering methods. `DynRender` is its object-safe counterpart, which
+//! every renderer implements, so that renderers may be chosen at runtime or
+//! stored in collections. Both `Box<DynRender>` and `&mut DynRender` implement
+//! `Render` in turn.
+//!
+//!``` rust
+//!# use hoedown::{Markdown, Render, Html, Trace};
+//!# use hoedown::renderer::dynamic::DynRender;
+//!# use hoedown::renderer::html::Flags;
+//!fn renderer(format: &str) -> Box<dyn DynRender> {
+//!    match format {
+//!        "trace" => Box::new(Trace),
+//!        _ => Box::new(Html::new(Flags::empty(), 0)),
+//!    }
+//!}
+//!
+//!let doc = Markdown::new("*hi*");
+//!
+//!assert_eq!(renderer("html").render(&doc).to_str().unwrap(), "<p><em>hi</em></p>\n");
+//!assert_eq!(renderer("trace").render(&doc).to_str().unwrap(), "MISSING PARAGRAPH HANDLER\n");
+//!```
+
+use buffer::Buffer;
+use ffi::hoedown_renderer;
+use markdown::Markdown;
+use super::{Render, Attributes, SourcePosition, AutoLink, Table, list};
+
+/// Object-safe counterpart of `Render`
+///
+/// Each method behaves as the `Render` method of the same name. This is
+/// implemented for every `Render` type, and shouldn't need to be implemented
+/// directly.
+pub trait DynRender {
+    /// `Render::render_to`, named differently so that calls to it aren't
+    /// ambiguous when both traits are in scope
+    fn dyn_render_to(&mut self, input: &Markdown, output: &mut Buffer);
+
+    /// `Render::render_inline_to`, named differently for the same reason
+    fn dyn_render_inline_to(&mut self, input: &Markdown, output: &mut Buffer);
+
+    unsafe fn to_hoedown(&mut self) -> hoedown_renderer;
+    fn code_block(&mut self, output: &mut Buffer, text: Option<&Buffer>, lang: Option<&Buffer>);
+    fn quote_block(&mut self, output: &mut Buffer, content: Option<&Buffer>);
+    fn header(&mut self, output: &mut Buffer, content: Option<&Buffer>, level: i32);
+    fn header_with_attributes(&mut self, output: &mut Buffer, content: Option<&Buffer>, level: i32, attributes: &Attributes);
+    fn horizontal_rule(&mut self, output: &mut Buffer);
+    fn list(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: list::List);
+    fn list_item(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: list::List);
+    fn paragraph(&mut self, output: &mut Buffer, content: Option<&Buffer>);
+    fn table(&mut self, output: &mut Buffer, content: Option<&Buffer>);
+    fn table_header(&mut self, output: &mut Buffer, content: Option<&Buffer>);
+    fn table_body(&mut self, output: &mut Buffer, content: Option<&Buffer>);
+    fn table_row(&mut self, output: &mut Buffer, content: Option<&Buffer>, columns: usize);
+    fn table_cell(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: Table, column: usize, columns: usize);
+    fn footnotes(&mut self, output: &mut Buffer, content: Option<&Buffer>);
+    fn footnote_definition(&mut self, output: &mut Buffer, content: Option<&Buffer>, num: u32);
+    fn html_block(&mut self, output: &mut Buffer, text: Option<&Buffer>);
+    fn positioned_block(&mut self, output: &mut Buffer, content: Option<&Buffer>, position: &SourcePosition);
+    fn definition_list(&mut self, output: &mut Buffer, content: Option<&Buffer>);
+    fn definition_term(&mut self, output: &mut Buffer, content: Option<&Buffer>);
+    fn definition_data(&mut self, output: &mut Buffer, content: Option<&Buffer>);
+    fn admonition(&mut self, output: &mut Buffer, content: Option<&Buffer>, kind: &Buffer, title: Option<&Buffer>);
+    fn autolink(&mut self, output: &mut Buffer, link: Option<&Buffer>, link_type: AutoLink) -> bool;
+    fn code_span(&mut self, output: &mut Buffer, text: Option<&Buffer>) -> bool;
+    fn double_emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool;
+    fn emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool;
+    fn underline(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool;
+    fn highlight(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool;
+    fn quote_span(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool;
+    fn image(&mut self, output: &mut Buffer, link: Option<&Buffer>, title: Option<&Buffer>, alt: Option<&Buffer>) -> bool;
+    fn line_break(&mut self, output: &mut Buffer) -> bool;
+    fn link(&mut self, output: &mut Buffer, content: Option<&Buffer>, link: Option<&Buffer>, title: Option<&Buffer>) -> bool;
+    fn wiki_link(&mut self, output: &mut Buffer, target: &Buffer, label: Option<&Buffer>) -> bool;
+    fn pattern_link(&mut self, output: &mut Buffer, text: &Buffer, link: &Buffer, name: &str) -> bool;
+    fn triple_emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool;
+    fn strikethrough(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool;
+    fn superscript(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool;
+    fn footnote_reference(&mut self, output: &mut Buffer, num: u32) -> bool;
+    fn math(&mut self, output: &mut Buffer, text: Option<&Buffer>, displaymode: i32) -> bool;
+    fn html_span(&mut self, output: &mut Buffer, text: Option<&Buffer>) -> bool;
+    fn entity(&mut self, output: &mut Buffer, text: Option<&Buffer>);
+    fn normal_text(&mut self, output: &mut Buffer, text: Option<&Buffer>);
+    fn before_render(&mut self, output: &mut Buffer, inline_render: bool);
+    fn after_render(&mut self, output: &mut Buffer, inline_render: bool);
+}
+
+impl<R> DynRender for R where R: Render {
+    fn dyn_render_to(&mut self, input: &Markdown, output: &mut Buffer) {
+        Render::render_to(self, input, output)
+    }
+    fn dyn_render_inline_to(&mut self, input: &Markdown, output: &mut Buffer) {
+        Render::render_inline_to(self, input, output)
+    }
+    unsafe fn to_hoedown(&mut self) -> hoedown_renderer {
+        Render::to_hoedown(self)
+    }
+    fn code_block(&mut self, output: &mut Buffer, text: Option<&Buffer>, lang: Option<&Buffer>) {
+        Render::code_block(self, output, text, lang)
+    }
+    fn quote_block(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        Render::quote_block(self, output, content)
+    }
+    fn header(&mut self, output: &mut Buffer, content: Option<&Buffer>, level: i32) {
+        Render::header(self, output, content, level)
+    }
+    fn header_with_attributes(&mut self, output: &mut Buffer, content: Option<&Buffer>, level: i32, attributes: &Attributes) {
+        Render::header_with_attributes(self, output, content, level, attributes)
+    }
+    fn horizontal_rule(&mut self, output: &mut Buffer) {
+        Render::horizontal_rule(self, output)
+    }
+    fn list(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: list::List) {
+        Render::list(self, output, content, flags)
+    }
+    fn list_item(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: list::List) {
+        Render::list_item(self, output, content, flags)
+    }
+    fn paragraph(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        Render::paragraph(self, output, content)
+    }
+    fn table(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        Render::table(self, output, content)
+    }
+    fn table_header(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        Render::table_header(self, output, content)
+    }
+    fn table_body(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        Render::table_body(self, output, content)
+    }
+    fn table_row(&mut self, output: &mut Buffer, content: Option<&Buffer>, columns: usize) {
+        Render::table_row(self, output, content, columns)
+    }
+    fn table_cell(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: Table, column: usize, columns: usize) {
+        Render::table_cell(self, output, content, flags, column, columns)
+    }
+    fn footnotes(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        Render::footnotes(self, output, content)
+    }
+    fn footnote_definition(&mut self, output: &mut Buffer, content: Option<&Buffer>, num: u32) {
+        Render::footnote_definition(self, output, content, num)
+    }
+    fn html_block(&mut self, output: &mut Buffer, text: Option<&Buffer>) {
+        Render::html_block(self, output, text)
+    }
+    fn positioned_block(&mut self, output: &mut Buffer, content: Option<&Buffer>, position: &SourcePosition) {
+        Render::positioned_block(self, output, content, position)
+    }
+    fn definition_list(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        Render::definition_list(self, output, content)
+    }
+    fn definition_term(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        Render::definition_term(self, output, content)
+    }
+    fn definition_data(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        Render::definition_data(self, output, content)
+    }
+    fn admonition(&mut self, output: &mut Buffer, content: Option<&Buffer>, kind: &Buffer, title: Option<&Buffer>) {
+        Render::admonition(self, output, content, kind, title)
+    }
+    fn autolink(&mut self, output: &mut Buffer, link: Option<&Buffer>, link_type: AutoLink) -> bool {
+        Render::autolink(self, output, link, link_type)
+    }
+    fn code_span(&mut self, output: &mut Buffer, text: Option<&Buffer>) -> bool {
+        Render::code_span(self, output, text)
+    }
+    fn double_emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+        Render::double_emphasis(self, output, content)
+    }
+    fn emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+        Render::emphasis(self, output, content)
+    }
+    fn underline(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+        Render::underline(self, output, content)
+    }
+    fn highlight(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+        Render::highlight(self, output, content)
+    }
+    fn quote_span(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+        Render::quote_span(self, output, content)
+    }
+    fn image(&mut self, output: &mut Buffer, link: Option<&Buffer>, title: Option<&Buffer>, alt: Option<&Buffer>) -> bool {
+        Render::image(self, output, link, title, alt)
+    }
+    fn line_break(&mut self, output: &mut Buffer) -> bool {
+        Render::line_break(self, output)
+    }
+    fn link(&mut self, output: &mut Buffer, content: Option<&Buffer>, link: Option<&Buffer>, title: Option<&Buffer>) -> bool {
+        Render::link(self, output, content, link, title)
+    }
+    fn wiki_link(&mut self, output: &mut Buffer, target: &Buffer, label: Option<&Buffer>) -> bool {
+        Render::wiki_link(self, output, target, label)
+    }
+    fn pattern_link(&mut self, output: &mut Buffer, text: &Buffer, link: &Buffer, name: &str) -> bool {
+        Render::pattern_link(self, output, text, link, name)
+    }
+    fn triple_emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+        Render::triple_emphasis(self, output, content)
+    }
+    fn strikethrough(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+        Render::strikethrough(self, output, content)
+    }
+    fn superscript(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+        Render::superscript(self, output, content)
+    }
+    fn footnote_reference(&mut self, output: &mut Buffer, num: u32) -> bool {
+        Render::footnote_reference(self, output, num)
+    }
+    fn math(&mut self, output: &mut Buffer, text: Option<&Buffer>, displaymode: i32) -> bool {
+        Render::math(self, output, text, displaymode)
+    }
+    fn html_span(&mut self, output: &mut Buffer, text: Option<&Buffer>) -> bool {
+        Render::html_span(self, output, text)
+    }
+    fn entity(&mut self, output: &mut Buffer, text: Option<&Buffer>) {
+        Render::entity(self, output, text)
+    }
+    fn normal_text(&mut self, output: &mut Buffer, text: Option<&Buffer>) {
+        Render::normal_text(self, output, text)
+    }
+    fn before_render(&mut self, output: &mut Buffer, inline_render: bool) {
+        Render::before_render(self, output, inline_render)
+    }
+    fn after_render(&mut self, output: &mut Buffer, inline_render: bool) {
+        Render::after_render(self, output, inline_render)
+    }
+}
+
+/// Implement `Render` for a pointer to a `DynRender` by forwarding every method
+macro_rules! render_dyn {
+    ([$($lifetime:tt),+] $($ty:tt)+) => {
+        impl<$($lifetime),+> Render for $($ty)+ {
+            fn render_to(&mut self, input: &Markdown, output: &mut Buffer) {
+                (**self).dyn_render_to(input, output)
+            }
+            fn render_inline_to(&mut self, input: &Markdown, output: &mut Buffer) {
+                (**self).dyn_render_inline_to(input, output)
+            }
+            unsafe fn to_hoedown(&mut self) -> hoedown_renderer {
+                (**self).to_hoedown()
+            }
+            fn code_block(&mut self, output: &mut Buffer, text: Option<&Buffer>, lang: Option<&Buffer>) {
+                (**self).code_block(output, text, lang)
+            }
+            fn quote_block(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+                (**self).quote_block(output, content)
+            }
+            fn header(&mut self, output: &mut Buffer, content: Option<&Buffer>, level: i32) {
+                (**self).header(output, content, level)
+            }
+            fn header_with_attributes(&mut self, output: &mut Buffer, content: Option<&Buffer>, level: i32, attributes: &Attributes) {
+                (**self).header_with_attributes(output, content, level, attributes)
+            }
+            fn horizontal_rule(&mut self, output: &mut Buffer) {
+                (**self).horizontal_rule(output)
+            }
+            fn list(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: list::List) {
+                (**self).list(output, content, flags)
+            }
+            fn list_item(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: list::List) {
+                (**self).list_item(output, content, flags)
+            }
+            fn paragraph(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+                (**self).paragraph(output, content)
+            }
+            fn table(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+                (**self).table(output, content)
+            }
+            fn table_header(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+                (**self).table_header(output, content)
+            }
+            fn table_body(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+                (**self).table_body(output, content)
+            }
+            fn table_row(&mut self, output: &mut Buffer, content: Option<&Buffer>, columns: usize) {
+                (**self).table_row(output, content, columns)
+            }
+            fn table_cell(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: Table, column: usize, columns: usize) {
+                (**self).table_cell(output, content, flags, column, columns)
+            }
+            fn footnotes(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+                (**self).footnotes(output, content)
+            }
+            fn footnote_definition(&mut self, output: &mut Buffer, content: Option<&Buffer>, num: u32) {
+                (**self).footnote_definition(output, content, num)
+            }
+            fn html_block(&mut self, output: &mut Buffer, text: Option<&Buffer>) {
+                (**self).html_block(output, text)
+            }
+            fn positioned_block(&mut self, output: &mut Buffer, content: Option<&Buffer>, position: &SourcePosition) {
+                (**self).positioned_block(output, content, position)
+            }
+            fn definition_list(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+                (**self).definition_list(output, content)
+            }
+            fn definition_term(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+                (**self).definition_term(output, content)
+            }
+            fn definition_data(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+                (**self).definition_data(output, content)
+            }
+            fn admonition(&mut self, output: &mut Buffer, content: Option<&Buffer>, kind: &Buffer, title: Option<&Buffer>) {
+                (**self).admonition(output, content, kind, title)
+            }
+            fn autolink(&mut self, output: &mut Buffer, link: Option<&Buffer>, link_type: AutoLink) -> bool {
+                (**self).autolink(output, link, link_type)
+            }
+            fn code_span(&mut self, output: &mut Buffer, text: Option<&Buffer>) -> bool {
+                (**self).code_span(output, text)
+            }
+            fn double_emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+                (**self).double_emphasis(output, content)
+            }
+            fn emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+                (**self).emphasis(output, content)
+            }
+            fn underline(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+                (**self).underline(output, content)
+            }
+            fn highlight(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+                (**self).highlight(output, content)
+            }
+            fn quote_span(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+                (**self).quote_span(output, content)
+            }
+            fn image(&mut self, output: &mut Buffer, link: Option<&Buffer>, title: Option<&Buffer>, alt: Option<&Buffer>) -> bool {
+                (**self).image(output, link, title, alt)
+            }
+            fn line_break(&mut self, output: &mut Buffer) -> bool {
+                (**self).line_break(output)
+            }
+            fn link(&mut self, output: &mut Buffer, content: Option<&Buffer>, link: Option<&Buffer>, title: Option<&Buffer>) -> bool {
+                (**self).link(output, content, link, title)
+            }
+            fn wiki_link(&mut self, output: &mut Buffer, target: &Buffer, label: Option<&Buffer>) -> bool {
+                (**self).wiki_link(output, target, label)
+            }
+            fn pattern_link(&mut self, output: &mut Buffer, text: &Buffer, link: &Buffer, name: &str) -> bool {
+                (**self).pattern_link(output, text, link, name)
+            }
+            fn triple_emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+                (**self).triple_emphasis(output, content)
+            }
+            fn strikethrough(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+                (**self).strikethrough(output, content)
+            }
+            fn superscript(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+                (**self).superscript(output, content)
+            }
+            fn footnote_reference(&mut self, output: &mut Buffer, num: u32) -> bool {
+                (**self).footnote_reference(output, num)
+            }
+            fn math(&mut self, output: &mut Buffer, text: Option<&Buffer>, displaymode: i32) -> bool {
+                (**self).math(output, text, displaymode)
+            }
+            fn html_span(&mut self, output: &mut Buffer, text: Option<&Buffer>) -> bool {
+                (**self).html_span(output, text)
+            }
+            fn entity(&mut self, output: &mut Buffer, text: Option<&Buffer>) {
+                (**self).entity(output, text)
+            }
+            fn normal_text(&mut self, output: &mut Buffer, text: Option<&Buffer>) {
+                (**self).normal_text(output, text)
+            }
+            fn before_render(&mut self, output: &mut Buffer, inline_render: bool) {
+                (**self).before_render(output, inline_render)
+            }
+            fn after_render(&mut self, output: &mut Buffer, inline_render: bool) {
+                (**self).after_render(output, inline_render)
+            }
+        }
+    }
+}
+
+render_dyn!(['a] Box<dyn DynRender + 'a>);
+render_dyn!(['a, 'b] &'b mut (dyn DynRender + 'a));
@@ -1,34 +1,61 @@
 //! Contains the html renderer and utilities
-use libc::c_void;
+use libc::{c_void, size_t};
 
 use std::ptr;
+use std::io::Write;
 
 use buffer::Buffer;
+use emoji::{self, Emoji};
+use extended::{self, Extended};
+use document::Document;
+use markdown::Markdown;
+use escape;
 use ffi::{
-    hoedown_buffer,
     hoedown_renderer,
     hoedown_html_renderer_new,
     hoedown_html_toc_renderer_new,
     hoedown_html_smartypants,
-    hoedown_html_renderer_free
+    hoedown_html_renderer_free,
 };
 
-use super::Render;
+use super::{Render, Attributes, SourcePosition};
+use super::list;
 
 /// Performs "smartypants" processing of the provided buffer.
 ///
 /// This turns, for example, straight quotes `"test"` into curly quotes `“test”`
 pub fn smartypants(content: &Buffer, output: &mut Buffer) {
-    let content: &hoedown_buffer = content.as_ref();
+    // hoedown looks at the character after a closing quote even at the end of
+    // the text, so it's given a copy followed by a nul
+    let mut text = Vec::with_capacity(content.len() + 1);
+    text.extend_from_slice(content);
+    text.push(0);
 
     unsafe {
         hoedown_html_smartypants(
             output.as_mut(),
-            content.data,
-            content.size);
+            text.as_ptr(),
+            content.len() as size_t);
     }
 }
 
+/// Remove trailing newlines from rendered content
+fn trim_newlines(content: &[u8]) -> &[u8] {
+    let end = content.iter().rposition(|&c| c != b'\n').map_or(0, |i| i + 1);
+    &content[.. end]
+}
+
+/// The page that a wiki link refers to, as determined by a wiki link resolver
+pub enum WikiPage {
+    /// The page exists at the given url
+    Found(String),
+
+    /// The page doesn't exist, but would be at the given url
+    ///
+    /// Links to missing pages are given the `missing` class.
+    Missing(String),
+}
+
 /// Flags to control the behavior of the html renderer
 bitflags! {
     /// Information about a list item
@@ -44,6 +71,13 @@ bitflags! {
 
         /// Output XHTML
         const USE_XHTML = 1 << 3,
+
+        // bindings-level: processed by these bindings rather than by hoedown
+
+        /// Add `data-sourcepos` attributes to top-level blocks
+        ///
+        /// Requires the `SOURCE_POSITIONS` extension.
+        const SOURCE_POSITIONS = 1 << 16,
     }
 }
 
@@ -67,6 +101,9 @@ bitflags! {
 ///```
 pub struct Html {
     renderer: *mut hoedown_renderer,
+    flags: Flags,
+    wiki_resolver: Option<Box<dyn FnMut(&str) -> WikiPage>>,
+    emoji: Option<emoji::Style>,
 }
 
 impl Html {
@@ -83,11 +120,14 @@ impl Html {
     /// to the `toc` method.
     pub fn new(flags: Flags, nesting_level: i32) -> Html {
         let renderer = unsafe {
-            hoedown_html_renderer_new(flags.bits(), nesting_level)
+            hoedown_html_renderer_new((flags - SOURCE_POSITIONS).bits(), nesting_level)
         };
 
         Html {
             renderer: renderer,
+            flags: flags,
+            wiki_resolver: None,
+            emoji: None,
         }
     }
 
@@ -105,9 +145,48 @@ impl Html {
 
         Html {
             renderer: renderer,
+            flags: Flags::empty(),
+            wiki_resolver: None,
+            emoji: None,
         }
     }
 
+    /// Set the function used to determine the url of the targets of wiki links.
+    ///
+    /// Without a resolver, the target itself is used as the url.
+    ///
+    ///``` rust
+    ///# use hoedown::{Markdown, Render, WIKI_LINKS};
+    ///# use hoedown::renderer::html::{Html, Flags, WikiPage};
+    ///let mut html = Html::new(Flags::empty(), 0);
+    ///
+    ///html.wiki_resolver(|target| {
+    ///    let url = format!("/wiki/{}", target.replace(" ", "_"));
+    ///
+    ///    if target == "Home" { WikiPage::Found(url) } else { WikiPage::Missing(url) }
+    ///});
+    ///
+    ///let doc = Markdown::new("[[Home]] and [[New Page|new]]").extensions(WIKI_LINKS);
+    ///
+    ///assert_eq!(html.render(&doc).to_str().unwrap(),
+    ///           "<p><a href=\"/wiki/Home\">Home</a> and \
+    ///            <a href=\"/wiki/New_Page\" class=\"missing\">new</a></p>\n");
+    ///```
+    pub fn wiki_resolver<F>(&mut self, resolver: F)
+    where F: FnMut(&str) -> WikiPage + 'static {
+        self.wiki_resolver = Some(Box::new(resolver));
+    }
+
+    /// Replace emoji shortcodes such as `:smile:` in the text with emoji
+    /// rendered in the given style.
+    ///
+    /// The renderer is wrapped in the `Emoji` wrapper whenever it renders a
+    /// document, so shortcodes aren't replaced when it's the base of another
+    /// wrapper. See the `emoji` module for details.
+    pub fn emoji(&mut self, style: emoji::Style) {
+        self.emoji = Some(style);
+    }
+
     // TODO: replace with AsRef/AsMut
     /// Get a reference to the underlying hoedown renderer
     pub fn get(&self) -> &hoedown_renderer {
@@ -118,9 +197,79 @@ impl Html {
     pub fn as_mut(&mut self) -> &mut hoedown_renderer {
         unsafe { &mut *self.renderer }
     }
+
+    /// Render a task list item, preceding its content with a disabled checkbox
+    fn task_list_item(&mut self, ob: &mut Buffer, content: Option<&Buffer>, flags: list::List) {
+        // e.g. the table of contents renderer doesn't render list items
+        if unsafe { (*self.renderer).listitem.is_none() } {
+            return;
+        }
+
+        let content = content.map_or(&[][..], |c| trim_newlines(c));
+
+        // block list items wrap their content in a paragraph
+        let (open, rest) =
+            if content.starts_with(b"<p>") { content.split_at(3) }
+            else { content.split_at(0) };
+
+        ob.write_all(b"<li class=\"task-list-item\">").unwrap();
+        ob.write_all(open).unwrap();
+
+        if self.flags.contains(USE_XHTML) {
+            ob.write_all(b"<input type=\"checkbox\" class=\"task-list-item-checkbox\" disabled=\"disabled\"").unwrap();
+
+            if flags.contains(list::CHECKED) {
+                ob.write_all(b" checked=\"checked\"").unwrap();
+            }
+
+            ob.write_all(b"/> ").unwrap();
+        } else {
+            ob.write_all(b"<input type=\"checkbox\" class=\"task-list-item-checkbox\" disabled").unwrap();
+
+            if flags.contains(list::CHECKED) {
+                ob.write_all(b" checked").unwrap();
+            }
+
+            ob.write_all(b"> ").unwrap();
+        }
+
+        ob.write_all(rest).unwrap();
+        ob.write_all(b"</li>\n").unwrap();
+    }
 }
 
 impl Render for Html {
+    fn render_to(&mut self, input: &Markdown, output: &mut Buffer) {
+        match self.emoji.take() {
+            // text has to pass through the emoji replacer
+            Some(style) => {
+                Emoji::new(&mut *self, style.clone()).render_to(input, output);
+                self.emoji = Some(style);
+            },
+            None if extended::is_required(input.extensions) =>
+                Extended::new(self, input.extensions).render_to(input, output),
+            None => {
+                let doc = Document::new(self.get(), input.extensions, input.max_nesting);
+                doc.render(&input.contents, output);
+            },
+        }
+    }
+
+    fn render_inline_to(&mut self, input: &Markdown, output: &mut Buffer) {
+        match self.emoji.take() {
+            Some(style) => {
+                Emoji::new(&mut *self, style.clone()).render_inline_to(input, output);
+                self.emoji = Some(style);
+            },
+            None if extended::is_required(input.extensions) =>
+                Extended::new(self, input.extensions).render_inline_to(input, output),
+            None => {
+                let doc = Document::new(self.get(), input.extensions, input.max_nesting);
+                doc.render_inline(&input.contents, output);
+            },
+        }
+    }
+
     unsafe fn to_hoedown(&mut self) -> hoedown_renderer {
         *self.renderer
     }
@@ -151,6 +300,72 @@ impl Render for Html {
         }
     }
 
+    fn header_with_attributes(&mut self, ob: &mut Buffer, content: Option<&Buffer>, level: i32, attributes: &Attributes) {
+        // e.g. the table of contents renderer doesn't render attributes
+        if unsafe { (*self.renderer).blockquote.is_none() } {
+            return self.header(ob, content, level);
+        }
+
+        let mut header = Buffer::new(64);
+        self.header(&mut header, content, level);
+
+        if header.is_empty() {
+            return;
+        }
+
+        if !ob.is_empty() {
+            ob.write_all(b"\n").unwrap();
+        }
+
+        // the opening tag is either `<hN>` or `<hN id="toc_M">`
+        let end = header.iter().position(|&c| c == b'>').unwrap_or(0);
+        let (tag, rest) = header.split_at(end);
+
+        // the table of contents links to the `toc_M` anchor, so it's kept
+        // within the header if the header's own id replaces it
+        let mut anchor = None;
+
+        match attributes.id {
+            Some(ref id) => {
+                if tag.len() > 3 {
+                    anchor = Some(&tag[4 ..]);
+                }
+
+                ob.write_all(&tag[.. 3]).unwrap();
+                ob.write_all(b" id=\"").unwrap();
+                escape::html(id.as_bytes(), ob, false);
+                ob.write_all(b"\"").unwrap();
+            },
+            None => {
+                ob.write_all(tag).unwrap();
+            },
+        }
+
+        if !attributes.classes.is_empty() {
+            ob.write_all(b" class=\"").unwrap();
+            escape::html(attributes.classes.join(" ").as_bytes(), ob, false);
+            ob.write_all(b"\"").unwrap();
+        }
+
+        for &(ref key, ref value) in &attributes.pairs {
+            write!(ob, " {}=\"", key).unwrap();
+            escape::html(value.as_bytes(), ob, false);
+            ob.write_all(b"\"").unwrap();
+        }
+
+        match anchor {
+            Some(anchor) => {
+                ob.write_all(b"><a ").unwrap();
+                ob.write_all(anchor).unwrap();
+                ob.write_all(b"></a>").unwrap();
+                ob.write_all(&rest[1 ..]).unwrap();
+            },
+            None => {
+                ob.write_all(rest).unwrap();
+            },
+        }
+    }
+
     fn horizontal_rule(&mut self, ob: &mut Buffer) {
         let data = self.renderer as *mut c_void;
 
@@ -168,6 +383,10 @@ impl Render for Html {
     }
 
     fn list_item(&mut self, ob: &mut Buffer, content: Option<&Buffer>, flags: ::renderer::list::List) {
+        if flags.contains(list::TASK) {
+            return self.task_list_item(ob, content, flags);
+        }
+
         let data = self.renderer as *mut c_void;
 
         if let Some(func) = unsafe { (*self.renderer).listitem } {
@@ -207,7 +426,7 @@ impl Render for Html {
         }
     }
 
-    fn table_row(&mut self, ob: &mut Buffer, content: Option<&Buffer>) {
+    fn table_row(&mut self, ob: &mut Buffer, content: Option<&Buffer>, _columns: usize) {
         let data = self.renderer as *mut c_void;
 
         if let Some(func) = unsafe { (*self.renderer).table_row } {
@@ -215,11 +434,11 @@ impl Render for Html {
         }
     }
 
-    fn table_cell(&mut self, ob: &mut Buffer, content: Option<&Buffer>, flags: ::renderer::Table) {
+    fn table_cell(&mut self, ob: &mut Buffer, content: Option<&Buffer>, flags: ::renderer::Table, _column: usize, _columns: usize) {
         let data = self.renderer as *mut c_void;
 
         if let Some(func) = unsafe { (*self.renderer).table_cell } {
-            func(ob.as_mut(), content.map_or(ptr::null(), |b| b.as_ref()), flags, data)
+            func(ob.as_mut(), content.map_or(ptr::null(), |b| b.as_ref()), flags.bits(), data)
         }
     }
 
@@ -247,6 +466,100 @@ impl Render for Html {
         }
     }
 
+    fn positioned_block(&mut self, ob: &mut Buffer, content: Option<&Buffer>, position: &SourcePosition) {
+        let content = match content {
+            Some(content) if !content.is_empty() => content,
+            _ => return,
+        };
+
+        // e.g. the table of contents renderer doesn't separate blocks
+        if unsafe { (*self.renderer).blockquote.is_none() } {
+            return ob.pipe(content);
+        }
+
+        if !ob.is_empty() {
+            ob.write_all(b"\n").unwrap();
+        }
+
+        // the end of the name of the first tag
+        let tag = content.windows(2).position(|w| w[0] == b'<' && w[1].is_ascii_alphabetic()).map(|start| {
+            start + 1 + content[start + 1 ..].iter().take_while(|c| c.is_ascii_alphanumeric()).count()
+        });
+
+        match tag {
+            Some(end) if self.flags.contains(SOURCE_POSITIONS) => {
+                ob.write_all(&content[.. end]).unwrap();
+                write!(ob, " data-sourcepos=\"{}:{}-{}:{}\"",
+                       position.start_line, position.start_column,
+                       position.end_line, position.end_column).unwrap();
+                ob.write_all(&content[end ..]).unwrap();
+            },
+            _ => ob.pipe(content),
+        }
+    }
+
+    fn definition_list(&mut self, ob: &mut Buffer, content: Option<&Buffer>) {
+        // e.g. the table of contents renderer doesn't render lists
+        if unsafe { (*self.renderer).list.is_none() } {
+            return;
+        }
+
+        if !ob.is_empty() {
+            ob.write_all(b"\n").unwrap();
+        }
+
+        ob.write_all(b"<dl>\n").unwrap();
+        content.map(|c| ob.pipe(c));
+        ob.write_all(b"</dl>\n").unwrap();
+    }
+
+    fn definition_term(&mut self, ob: &mut Buffer, content: Option<&Buffer>) {
+        ob.write_all(b"<dt>").unwrap();
+        content.map(|c| ob.write_all(trim_newlines(c)).unwrap());
+        ob.write_all(b"</dt>\n").unwrap();
+    }
+
+    fn definition_data(&mut self, ob: &mut Buffer, content: Option<&Buffer>) {
+        ob.write_all(b"<dd>").unwrap();
+        content.map(|c| ob.write_all(trim_newlines(c)).unwrap());
+        ob.write_all(b"</dd>\n").unwrap();
+    }
+
+    fn admonition(&mut self, ob: &mut Buffer, content: Option<&Buffer>, kind: &Buffer, title: Option<&Buffer>) {
+        // e.g. the table of contents renderer doesn't render blockquotes
+        if unsafe { (*self.renderer).blockquote.is_none() } {
+            return;
+        }
+
+        if !ob.is_empty() {
+            ob.write_all(b"\n").unwrap();
+        }
+
+        ob.write_all(b"<div class=\"admonition ").unwrap();
+        ob.pipe(kind);
+        ob.write_all(b"\">\n").unwrap();
+
+        match title {
+            Some(title) => if !title.is_empty() {
+                ob.write_all(b"<p class=\"admonition-title\">").unwrap();
+                ob.write_all(trim_newlines(title)).unwrap();
+                ob.write_all(b"</p>\n").unwrap();
+            },
+            None => if !kind.is_empty() {
+                // default to the capitalized kind
+                let (first, rest) = kind.split_at(1);
+
+                ob.write_all(b"<p class=\"admonition-title\">").unwrap();
+                ob.write_all(&first.to_ascii_uppercase()).unwrap();
+                ob.write_all(rest).unwrap();
+                ob.write_all(b"</p>\n").unwrap();
+            },
+        }
+
+        content.map(|c| ob.pipe(c));
+        ob.write_all(b"</div>\n").unwrap();
+    }
+
     fn autolink(&mut self, ob: &mut Buffer, link: Option<&Buffer>, ty: ::renderer::AutoLink) -> bool {
         let data = self.renderer as *mut c_void;
 
@@ -355,6 +668,39 @@ impl Render for Html {
         }
     }
 
+    fn wiki_link(&mut self, ob: &mut Buffer, target: &Buffer, label: Option<&Buffer>) -> bool {
+        // e.g. the table of contents renderer only renders the content of links
+        if unsafe { (*self.renderer).blockquote.is_none() } {
+            label.map(|l| ob.pipe(l));
+            return true;
+        }
+
+        let target = String::from_utf8_lossy(target).into_owned();
+
+        let page = match self.wiki_resolver {
+            Some(ref mut resolver) => resolver(&target),
+            None => WikiPage::Found(target),
+        };
+
+        ob.write_all(b"<a href=\"").unwrap();
+
+        match page {
+            WikiPage::Found(url) => {
+                escape::href(url.as_bytes(), ob);
+                ob.write_all(b"\">").unwrap();
+            },
+            WikiPage::Missing(url) => {
+                escape::href(url.as_bytes(), ob);
+                ob.write_all(b"\" class=\"missing\">").unwrap();
+            },
+        }
+
+        label.map(|l| ob.pipe(l));
+        ob.write_all(b"</a>").unwrap();
+
+        true
+    }
+
     fn triple_emphasis(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
         let data = self.renderer as *mut c_void;
 
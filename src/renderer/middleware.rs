@@ -0,0 +1,249 @@
+//! Stacks of middleware
+//!
+//! A `Wrapper` wraps exactly one base renderer, so combining several of them
+//! means nesting their types. A `Stack` instead holds any number of
+//! `Middleware` layers, which may be chosen at runtime, in front of a base
+//! renderer. Each layer may intercept any callback, and render it with the
+//! rest of the stack by calling the same method of `next`.
+//!
+//!``` rust
+//!# use hoedown::{Markdown, Render, Buffer, Html};
+//!# use hoedown::renderer::html::Flags;
+//!# use hoedown::renderer::middleware::{Stack, Middleware, Next};
+//!struct Absolute;
+//!
+//!impl Middleware for Absolute {
+//!    fn link(&mut self, next: &mut Next, output: &mut Buffer, content: Option<&Buffer>, link: Option<&Buffer>, title: Option<&Buffer>) -> bool {
+//!        let mut absolute = Buffer::from("https://example.com");
+//!        link.map(|l| absolute.pipe(l));
+//!        next.link(output, content, Some(&absolute), title)
+//!    }
+//!}
+//!
+//!struct Shout;
+//!
+//!impl Middleware for Shout {
+//!    fn normal_text(&mut self, next: &mut Next, output: &mut Buffer, text: Option<&Buffer>) {
+//!        let upper = text.map(|t| Buffer::from(&t.to_str().unwrap().to_uppercase()[..]));
+//!        next.normal_text(output, upper.as_ref())
+//!    }
+//!}
+//!
+//!let doc = Markdown::new("see [docs](/docs)\n");
+//!let mut stack = Stack::new(Html::new(Flags::empty(), 0)).layer(Absolute);
+//!stack.push(Shout);
+//!
+//!assert_eq!(stack.render(&doc).to_str().unwrap(),
+//!           "<p>SEE <a href=\"https://example.com/docs\">DOCS</a></p>\n");
+//!```
+
+use buffer::Buffer;
+use super::{Render, Attributes, SourcePosition, AutoLink, Table, list};
+use super::dynamic::DynRender;
+use super::wrapper::Wrapper;
+
+/// Define the middleware trait, the dispatch to the next layer, and the
+/// stack's callbacks
+macro_rules! middleware {
+    ($($(#[$doc:meta])* fn $name:ident($($arg:ident: $ty:ty),*) $(-> $ret:ty)*;)+) => {
+        /// A layer of a `Stack`
+        ///
+        /// Each method takes the same arguments as the `Render` method of the
+        /// same name, preceded by the rest of the stack. By default, each
+        /// callback is passed on to the rest of the stack unchanged.
+        pub trait Middleware {
+            $(
+                $(#[$doc])*
+                fn $name(&mut self, next: &mut Next, output: &mut Buffer, $($arg: $ty),*) $(-> $ret)* {
+                    next.$name(output, $($arg),*)
+                }
+            )+
+        }
+
+        impl<'a> Next<'a> {
+            $(
+                $(#[$doc])*
+                pub fn $name(&mut self, output: &mut Buffer, $($arg: $ty),*) $(-> $ret)* {
+                    match self.layers.split_first_mut() {
+                        Some((layer, layers)) => {
+                            let mut next = Next { layers: layers, base: &mut *self.base };
+                            layer.$name(&mut next, output, $($arg),*)
+                        },
+                        None => self.base.$name(output, $($arg),*),
+                    }
+                }
+            )+
+        }
+
+        impl<R> Wrapper for Stack<R> where R: Render {
+            type Base = R;
+
+            #[inline(always)]
+            fn base(&mut self) -> &mut R {
+                &mut self.base
+            }
+
+            $(
+                fn $name(&mut self, output: &mut Buffer, $($arg: $ty),*) $(-> $ret)* {
+                    self.next().$name(output, $($arg),*)
+                }
+            )+
+        }
+    }
+}
+
+/// The rest of a stack, below the current layer
+///
+/// Its methods render with the next layer, or the base renderer after the
+/// last layer.
+pub struct Next<'a> {
+    layers: &'a mut [Box<dyn Middleware>],
+    base: &'a mut dyn DynRender,
+}
+
+/// Renderer which renders with layers of middleware in front of a base renderer
+///
+/// Callbacks reach the layers in the order in which they were added.
+pub struct Stack<R> {
+    layers: Vec<Box<dyn Middleware>>,
+    base: R,
+}
+
+impl<R> Stack<R> where R: Render {
+    /// Construct a stack without any layers in front of the base renderer
+    pub fn new(base: R) -> Stack<R> {
+        Stack {
+            layers: vec![],
+            base: base,
+        }
+    }
+
+    /// Builder method to add a layer below the existing ones
+    pub fn layer<M>(mut self, layer: M) -> Stack<R>
+    where M: Middleware + 'static {
+        self.push(layer);
+        self
+    }
+
+    /// Add a layer below the existing ones
+    pub fn push<M>(&mut self, layer: M)
+    where M: Middleware + 'static {
+        self.layers.push(Box::new(layer));
+    }
+
+    /// The number of layers
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Whether there are no layers
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Unwrap the base renderer
+    pub fn into_inner(self) -> R {
+        self.base
+    }
+
+    fn next<'a>(&'a mut self) -> Next<'a> {
+        Next {
+            layers: &mut self.layers,
+            base: &mut self.base,
+        }
+    }
+}
+
+wrap!([R: Render] Stack<R>);
+
+middleware! {
+    /// Render code blocks
+    fn code_block(code: Option<&Buffer>, lang: Option<&Buffer>);
+    /// Render block quotes
+    fn quote_block(content: Option<&Buffer>);
+    /// Render headers
+    fn header(content: Option<&Buffer>, level: i32);
+    /// Render headers with attributes
+    fn header_with_attributes(content: Option<&Buffer>, level: i32, attributes: &Attributes);
+    /// Render horizontal rules
+    fn horizontal_rule();
+    /// Render lists
+    fn list(content: Option<&Buffer>, flags: list::List);
+    /// Render list items
+    fn list_item(content: Option<&Buffer>, flags: list::List);
+    /// Render paragraphs
+    fn paragraph(content: Option<&Buffer>);
+    /// Render tables
+    fn table(content: Option<&Buffer>);
+    /// Render table headers
+    fn table_header(content: Option<&Buffer>);
+    /// Render table bodies
+    fn table_body(content: Option<&Buffer>);
+    /// Render table rows
+    fn table_row(content: Option<&Buffer>, columns: usize);
+    /// Render table cells
+    fn table_cell(content: Option<&Buffer>, flags: Table, column: usize, columns: usize);
+    /// Render the footnotes section
+    fn footnotes(content: Option<&Buffer>);
+    /// Render footnote definitions
+    fn footnote_definition(content: Option<&Buffer>, num: u32);
+    /// Render html blocks
+    fn html_block(text: Option<&Buffer>);
+    /// Render positioned blocks
+    fn positioned_block(content: Option<&Buffer>, position: &SourcePosition);
+    /// Render definition lists
+    fn definition_list(content: Option<&Buffer>);
+    /// Render definition terms
+    fn definition_term(content: Option<&Buffer>);
+    /// Render definition data
+    fn definition_data(content: Option<&Buffer>);
+    /// Render admonitions
+    fn admonition(content: Option<&Buffer>, kind: &Buffer, title: Option<&Buffer>);
+
+    /// Render autolinks
+    fn autolink(link: Option<&Buffer>, link_type: AutoLink) -> bool;
+    /// Render code spans
+    fn code_span(text: Option<&Buffer>) -> bool;
+    /// Render double emphasis
+    fn double_emphasis(content: Option<&Buffer>) -> bool;
+    /// Render emphasis
+    fn emphasis(content: Option<&Buffer>) -> bool;
+    /// Render underlines
+    fn underline(content: Option<&Buffer>) -> bool;
+    /// Render highlights
+    fn highlight(content: Option<&Buffer>) -> bool;
+    /// Render quote spans
+    fn quote_span(content: Option<&Buffer>) -> bool;
+    /// Render images
+    fn image(link: Option<&Buffer>, title: Option<&Buffer>, alt: Option<&Buffer>) -> bool;
+    /// Render line breaks
+    fn line_break() -> bool;
+    /// Render links
+    fn link(content: Option<&Buffer>, link: Option<&Buffer>, title: Option<&Buffer>) -> bool;
+    /// Render wiki links
+    fn wiki_link(target: &Buffer, label: Option<&Buffer>) -> bool;
+    /// Render pattern links
+    fn pattern_link(text: &Buffer, link: &Buffer, name: &str) -> bool;
+    /// Render triple emphasis
+    fn triple_emphasis(content: Option<&Buffer>) -> bool;
+    /// Render strikethroughs
+    fn strikethrough(content: Option<&Buffer>) -> bool;
+    /// Render superscripts
+    fn superscript(content: Option<&Buffer>) -> bool;
+    /// Render footnote references
+    fn footnote_reference(num: u32) -> bool;
+    /// Render math
+    fn math(text: Option<&Buffer>, displaymode: i32) -> bool;
+    /// Render html spans
+    fn html_span(text: Option<&Buffer>) -> bool;
+
+    /// Render entities
+    fn entity(text: Option<&Buffer>);
+    /// Render normal text
+    fn normal_text(text: Option<&Buffer>);
+
+    /// Runs before rendering
+    fn before_render(inline_render: bool);
+    /// Runs after rendering
+    fn after_render(inline_render: bool);
+}
@@ -1,11 +1,14 @@
 //! Contains Render behavior and stock renderers
 
+use std::ops::Range;
+
 use wrappers;
 use buffer::Buffer;
 use ffi::hoedown_renderer;
 
 use markdown::Markdown;
 use document::Document;
+use extended::{self, Extended};
 
 /// Represents render behavior
 ///
@@ -84,6 +87,40 @@ use document::Document;
 ///    "this <em>one</em> that <em>two</em> another <em>three</em> pass it <em>around</em>",
 ///    output.to_str().unwrap());
 ///```
+///
+/// Renderers that write html themselves should escape the text and urls they
+/// are given with the functions in the `escape` module.
+///
+///``` rust
+///# use std::io::Write;
+///# use hoedown::{Markdown, Buffer, Render, escape};
+///struct ExternalLinks;
+///
+///impl Render for ExternalLinks {
+///    fn paragraph(&mut self, ob: &mut Buffer, content: Option<&Buffer>) {
+///        content.map(|c| ob.pipe(c));
+///    }
+///
+///    fn link(&mut self, ob: &mut Buffer, content: Option<&Buffer>, link: Option<&Buffer>, _: Option<&Buffer>) -> bool {
+///        ob.write_all(b"<a class=\"external\" href=\"").unwrap();
+///        link.map(|l| escape::href(l, ob));
+///        ob.write_all(b"\">").unwrap();
+///        content.map(|c| ob.pipe(c));
+///        ob.write_all(b"</a>").unwrap();
+///        true
+///    }
+///
+///    fn normal_text(&mut self, ob: &mut Buffer, text: Option<&Buffer>) {
+///        text.map(|t| escape::html(t, ob, false));
+///    }
+///}
+///
+///let doc = Markdown::new("[Tom & Jerry](http://example.com/search?q=cat&page=2)");
+///
+///assert_eq!(
+///    ExternalLinks.render(&doc).to_str().unwrap(),
+///    "<a class=\"external\" href=\"http://example.com/search?q=cat&amp;page=2\">Tom &amp; Jerry</a>");
+///```
 
 #[allow(unused_variables)]
 pub trait Render: Sized {
@@ -96,6 +133,10 @@ pub trait Render: Sized {
 
     /// Render the document into the given buffer
     fn render_to(&mut self, input: &Markdown, output: &mut Buffer) {
+        if extended::is_required(input.extensions) {
+            return Extended::new(self, input.extensions).render_to(input, output);
+        }
+
         let renderer = unsafe { self.to_hoedown() };
         let doc = Document::new(&renderer, input.extensions.clone(), input.max_nesting);
         doc.render(&input.contents, output);
@@ -110,6 +151,10 @@ pub trait Render: Sized {
 
     /// Render the document as inline into the given buffer
     fn render_inline_to(&mut self, input: &Markdown, output: &mut Buffer) {
+        if extended::is_required(input.extensions) {
+            return Extended::new(self, input.extensions).render_inline_to(input, output);
+        }
+
         let renderer = unsafe { self.to_hoedown() };
         let doc = Document::new(&renderer, input.extensions.clone(), input.max_nesting);
         doc.render_inline(&input.contents, output);
@@ -128,53 +173,7 @@ pub trait Render: Sized {
     /// to ensure that the renderer outlives the document (the document is what
     /// requires `to_hoedown`)
     unsafe fn to_hoedown(&mut self) -> hoedown_renderer {
-        use libc::c_void;
-
-        let renderer = hoedown_renderer {
-            opaque: self as *mut _ as *mut c_void,
-
-            // block-level handlers are unconditionally registered
-            blockcode: Some(wrappers::blockcode::<Self>),
-            blockquote: Some(::wrappers::blockquote::<Self>),
-            header: Some(wrappers::header::<Self>),
-            hrule: Some(wrappers::hrule::<Self>),
-            list: Some(wrappers::list::<Self>),
-            listitem: Some(wrappers::listitem::<Self>),
-            paragraph: Some(wrappers::paragraph::<Self>),
-            table: Some(wrappers::table::<Self>),
-            table_header: Some(wrappers::table_header::<Self>),
-            table_body: Some(wrappers::table_body::<Self>),
-            table_row: Some(wrappers::table_row::<Self>),
-            table_cell: Some(wrappers::table_cell::<Self>),
-            footnotes: Some(wrappers::footnotes::<Self>),
-            footnote_def: Some(wrappers::footnote_def::<Self>),
-            blockhtml: Some(wrappers::blockhtml::<Self>),
-
-            autolink: Some(wrappers::autolink::<Self>),
-            codespan: Some(wrappers::codespan::<Self>),
-            double_emphasis: Some(wrappers::double_emphasis::<Self>),
-            emphasis: Some(wrappers::emphasis::<Self>),
-            underline: Some(wrappers::underline::<Self>),
-            highlight: Some(wrappers::highlight::<Self>),
-            quote: Some(wrappers::quote::<Self>),
-            image: Some(wrappers::image::<Self>),
-            linebreak: Some(wrappers::linebreak::<Self>),
-            link: Some(wrappers::link::<Self>),
-            triple_emphasis: Some(wrappers::triple_emphasis::<Self>),
-            strikethrough: Some(wrappers::strikethrough::<Self>),
-            superscript: Some(wrappers::superscript::<Self>),
-            footnote_ref: Some(wrappers::footnote_ref::<Self>),
-            math: Some(wrappers::math::<Self>),
-            raw_html: Some(wrappers::raw_html::<Self>),
-
-            entity: Some(wrappers::entity::<Self>),
-            normal_text: Some(wrappers::normal_text::<Self>),
-
-            doc_header: Some(wrappers::doc_header::<Self>),
-            doc_footer: Some(wrappers::doc_footer::<Self>),
-        };
-
-        return renderer;
+        wrappers::renderer(self)
     }
 
     // block-level: not registered = skip the block
@@ -199,6 +198,18 @@ pub trait Render: Sized {
     /// The default implementation outputs an error string.
     fn header(&mut self, output: &mut Buffer, content: Option<&Buffer>, level: i32) {}
 
+    /// Runs when a header with an attribute block is encountered,
+    /// e.g. `## Install {#install .important}`
+    ///
+    /// The attribute block is removed from the content.
+    ///
+    /// Only runs if the `HEADER_ATTRIBUTES` extension is enabled.
+    ///
+    /// The default implementation ignores the attributes and calls `header`.
+    fn header_with_attributes(&mut self, output: &mut Buffer, content: Option<&Buffer>, level: i32, attributes: &Attributes) {
+        self.header(output, content, level)
+    }
+
     /// Runs when a horizontal rule is encountered
     ///
     /// The default implementation outputs an error string.
@@ -211,6 +222,9 @@ pub trait Render: Sized {
 
     /// Runs when a list item is encountered.
     ///
+    /// If the `TASK_LISTS` extension is enabled, task list items have the
+    /// `TASK` flag set and their marker stripped from the content.
+    ///
     /// The default implementation outputs an error string.
     fn list_item(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: ::renderer::list::List) {}
 
@@ -242,17 +256,22 @@ pub trait Render: Sized {
 
     /// Runs when a table row is encountered.
     ///
+    /// Every row of a table has the same number of `columns`, since hoedown
+    /// pads short rows with empty cells and drops the extra cells of long rows.
+    ///
     /// Only runs if the `TABLES` extension is enabled.
     ///
     /// The default implementation outputs an error string.
-    fn table_row(&mut self, output: &mut Buffer, content: Option<&Buffer>) {}
+    fn table_row(&mut self, output: &mut Buffer, content: Option<&Buffer>, columns: usize) {}
 
     /// Runs when a table cell is encountered.
     ///
+    /// The `column` is the index of the cell within its row, out of `columns`.
+    ///
     /// Only runs if the `TABLES` extension is enabled.
     ///
     /// The default implementation outputs an error string.
-    fn table_cell(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: ::renderer::Table) {}
+    fn table_cell(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: ::renderer::Table, column: usize, columns: usize) {}
 
     /// Runs when footnotes are encountered.
     ///
@@ -273,6 +292,60 @@ pub trait Render: Sized {
     /// The default implementation outputs an error string.
     fn html_block(&mut self, output: &mut Buffer, text: Option<&Buffer>) {}
 
+    /// Runs with the rendered output of each top-level block, along with the
+    /// block's position in the document.
+    ///
+    /// The content may consist of more than one block if they aren't separated
+    /// by a blank line, e.g. a paragraph that's directly followed by a list.
+    /// Other callbacks, including those of the blocks and spans within the
+    /// content, don't receive positions, and neither does the footnotes section.
+    /// Blocks that render nothing, e.g. reference definitions, are still
+    /// reported, with empty content.
+    ///
+    /// Only runs if the `SOURCE_POSITIONS` extension is enabled.
+    ///
+    /// The default implementation passes the content to the output buffer.
+    fn positioned_block(&mut self, output: &mut Buffer, content: Option<&Buffer>, position: &SourcePosition) {
+        content.map(|c| output.pipe(c));
+    }
+
+    /// Runs when a definition list is encountered.
+    ///
+    /// The content consists of the output of the `definition_term` and
+    /// `definition_data` callbacks for each of the list's items.
+    ///
+    /// Only runs if the `DEFINITION_LISTS` extension is enabled.
+    ///
+    /// The default implementation outputs an error string.
+    fn definition_list(&mut self, output: &mut Buffer, content: Option<&Buffer>) {}
+
+    /// Runs when a term of a definition list is encountered.
+    ///
+    /// Only runs if the `DEFINITION_LISTS` extension is enabled.
+    ///
+    /// The default implementation outputs an error string.
+    fn definition_term(&mut self, output: &mut Buffer, content: Option<&Buffer>) {}
+
+    /// Runs when a definition of a definition list is encountered.
+    ///
+    /// The content is rendered inline unless the definition is preceded by a
+    /// blank line or consists of multiple paragraphs.
+    ///
+    /// Only runs if the `DEFINITION_LISTS` extension is enabled.
+    ///
+    /// The default implementation outputs an error string.
+    fn definition_data(&mut self, output: &mut Buffer, content: Option<&Buffer>) {}
+
+    /// Runs when an admonition such as a note or a warning is encountered.
+    ///
+    /// The kind is in lowercase, e.g. `warning`. The title is rendered inline
+    /// and is only present if one was given.
+    ///
+    /// Only runs if the `ADMONITIONS` extension is enabled.
+    ///
+    /// The default implementation outputs an error string.
+    fn admonition(&mut self, output: &mut Buffer, content: Option<&Buffer>, kind: &Buffer, title: Option<&Buffer>) {}
+
     // span-level: not registered = pass-through
 
     /// Runs when an autolink candidate is encountered.
@@ -359,6 +432,29 @@ pub trait Render: Sized {
         false
     }
 
+    /// Runs when a wiki link is encountered.
+    ///
+    /// e.g. `[[target]]` or `[[target|label]]`, where the label defaults to the target
+    ///
+    /// Only runs if the `WIKI_LINKS` extension is enabled.
+    ///
+    /// The default implementation passes the context markdown to the buffer verbatim.
+    fn wiki_link(&mut self, output: &mut Buffer, target: &Buffer, label: Option<&Buffer>) -> bool {
+        false
+    }
+
+    /// Runs when text matches a custom autolink pattern.
+    ///
+    /// e.g. `#1234` for a pattern of issue references, where `name` is the
+    /// name that the pattern was added with
+    ///
+    /// Only runs within the `autolink::Patterns` wrapper.
+    ///
+    /// The default implementation lets the text be rendered by the `link` callback.
+    fn pattern_link(&mut self, output: &mut Buffer, text: &Buffer, link: &Buffer, name: &str) -> bool {
+        false
+    }
+
     /// Runs when triple emphasis is encountered.
     ///
     /// e.g. `***strongly emphasized***`
@@ -441,6 +537,12 @@ pub trait Render: Sized {
 }
 
 impl<'a, R> Render for &'a mut R where R: Render {
+    fn render_to(&mut self, input: &Markdown, output: &mut Buffer) {
+        (**self).render_to(input, output)
+    }
+    fn render_inline_to(&mut self, input: &Markdown, output: &mut Buffer) {
+        (**self).render_inline_to(input, output)
+    }
     unsafe fn to_hoedown(&mut self) -> hoedown_renderer {
         (**self).to_hoedown()
     }
@@ -455,6 +557,9 @@ impl<'a, R> Render for &'a mut R where R: Render {
     fn header(&mut self, output: &mut Buffer, content: Option<&Buffer>, level: i32) {
         (**self).header(output, content, level)
     }
+    fn header_with_attributes(&mut self, output: &mut Buffer, content: Option<&Buffer>, level: i32, attributes: &Attributes) {
+        (**self).header_with_attributes(output, content, level, attributes)
+    }
     fn horizontal_rule(&mut self, output: &mut Buffer) {
         (**self).horizontal_rule(output)
     }
@@ -476,11 +581,11 @@ impl<'a, R> Render for &'a mut R where R: Render {
     fn table_body(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
         (**self).table_body(output, content)
     }
-    fn table_row(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
-        (**self).table_row(output, content)
+    fn table_row(&mut self, output: &mut Buffer, content: Option<&Buffer>, columns: usize) {
+        (**self).table_row(output, content, columns)
     }
-    fn table_cell(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: Table) {
-        (**self).table_cell(output, content, flags)
+    fn table_cell(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: Table, column: usize, columns: usize) {
+        (**self).table_cell(output, content, flags, column, columns)
     }
     fn footnotes(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
         (**self).footnotes(output, content)
@@ -491,6 +596,21 @@ impl<'a, R> Render for &'a mut R where R: Render {
     fn html_block(&mut self, output: &mut Buffer, text: Option<&Buffer>) {
         (**self).html_block(output, text)
     }
+    fn positioned_block(&mut self, output: &mut Buffer, content: Option<&Buffer>, position: &SourcePosition) {
+        (**self).positioned_block(output, content, position)
+    }
+    fn definition_list(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        (**self).definition_list(output, content)
+    }
+    fn definition_term(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        (**self).definition_term(output, content)
+    }
+    fn definition_data(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        (**self).definition_data(output, content)
+    }
+    fn admonition(&mut self, output: &mut Buffer, content: Option<&Buffer>, kind: &Buffer, title: Option<&Buffer>) {
+        (**self).admonition(output, content, kind, title)
+    }
 
     // span-level: not registered = pass-through
     fn autolink(&mut self, output: &mut Buffer, link: Option<&Buffer>, link_type: AutoLink) -> bool {
@@ -523,6 +643,12 @@ impl<'a, R> Render for &'a mut R where R: Render {
     fn link(&mut self, output: &mut Buffer, content: Option<&Buffer>, link: Option<&Buffer>, title: Option<&Buffer>) -> bool {
         (**self).link(output, content, link, title)
     }
+    fn wiki_link(&mut self, output: &mut Buffer, target: &Buffer, label: Option<&Buffer>) -> bool {
+        (**self).wiki_link(output, target, label)
+    }
+    fn pattern_link(&mut self, output: &mut Buffer, text: &Buffer, link: &Buffer, name: &str) -> bool {
+        (**self).pattern_link(output, text, link, name)
+    }
     fn triple_emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
         (**self).triple_emphasis(output, content)
     }
@@ -572,6 +698,16 @@ pub mod list {
 
             /// A list item that contains a block
             const BLOCK   = 1 << 1,
+
+            /// A task list item, e.g. `- [ ] item`
+            ///
+            /// Only set if the `TASK_LISTS` extension is enabled.
+            const TASK    = 1 << 2,
+
+            /// A task list item that is checked, e.g. `- [x] item`
+            ///
+            /// Only set if the `TASK_LISTS` extension is enabled.
+            const CHECKED = 1 << 3,
         }
     }
 
@@ -579,29 +715,118 @@ pub mod list {
         pub fn from_arbitrary_bits(bits: u32) -> List {
             List { bits: bits }
         }
+
+        /// Whether a task list item is checked, or `None` if it's not a task list item
+        pub fn checked(&self) -> Option<bool> {
+            if self.contains(TASK) {
+                Some(self.contains(CHECKED))
+            } else {
+                None
+            }
+        }
     }
 }
 
-/// The table alignment or position
-#[derive(Debug, Copy, Clone)]
-#[repr(C)]
-pub enum Table {
-    Left = 1,
-    Right,
-    Center,
-    Mask,
-    Header,
+/// Flags that describe a table cell
+pub mod table {
+    bitflags! {
+        /// Flags that describe a table cell
+        ///
+        /// A centered column has both of the `ALIGN_LEFT` and `ALIGN_RIGHT`
+        /// flags, so its alignment is best determined with `alignment`.
+        pub flags Table: u32 {
+            /// A cell of a column that is aligned left, e.g. `:---`
+            const ALIGN_LEFT   = 1 << 0,
+
+            /// A cell of a column that is aligned right, e.g. `---:`
+            const ALIGN_RIGHT  = 1 << 1,
+
+            /// A cell of a column that is centered, e.g. `:---:`
+            const ALIGN_CENTER = ALIGN_LEFT.bits | ALIGN_RIGHT.bits,
+
+            /// A cell of the header row
+            const HEADER       = 1 << 2,
+        }
+    }
+
+    /// The alignment of a table column
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub enum Alignment {
+        /// No alignment was specified, e.g. `---`
+        None,
+        Left,
+        Right,
+        Center,
+    }
+
+    impl Table {
+        /// The alignment of the cell's column
+        pub fn alignment(&self) -> Alignment {
+            match (self.contains(ALIGN_LEFT), self.contains(ALIGN_RIGHT)) {
+                (true, true) => Alignment::Center,
+                (true, false) => Alignment::Left,
+                (false, true) => Alignment::Right,
+                (false, false) => Alignment::None,
+            }
+        }
+
+        /// Whether the cell is in the header row
+        pub fn is_header(&self) -> bool {
+            self.contains(HEADER)
+        }
+    }
+}
+
+pub use self::table::Table;
+
+/// Attributes given to an element by an attribute block, e.g. `{#id .class key=value}`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Attributes {
+    /// The id, e.g. `#id`
+    pub id: Option<String>,
+
+    /// The classes, e.g. `.class`
+    pub classes: Vec<String>,
+
+    /// Any other attributes, e.g. `key=value` or `key="some value"`
+    pub pairs: Vec<(String, String)>,
+}
+
+/// The position of an element within the source document
+///
+/// Lines and columns start from 1, and columns are counted in bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourcePosition {
+    /// The byte range of the element
+    pub range: Range<usize>,
+
+    /// The line of the element's first character
+    pub start_line: usize,
+
+    /// The column of the element's first character
+    pub start_column: usize,
+
+    /// The line of the element's last character
+    pub end_line: usize,
+
+    /// The column of the element's last character
+    pub end_column: usize,
 }
 
 /// The type of an autolink candidate
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(C)]
 pub enum AutoLink {
     Normal = 1,
     Email,
 }
 
+#[macro_use]
 pub mod wrapper;
+pub mod dynamic;
+pub mod builder;
+pub mod middleware;
 pub mod html;
+pub mod page;
 pub mod trace;
 
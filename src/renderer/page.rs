@@ -0,0 +1,244 @@
+//! Standalone html pages
+//!
+//! The `Html` renderer produces a fragment meant to be embedded in a page. A
+//! `Page` instead wraps the rendered document in a complete HTML5 document.
+//!
+//!``` rust
+//!# use hoedown::Markdown;
+//!# use hoedown::renderer::html::{self, Html};
+//!# use hoedown::renderer::page::Page;
+//!let doc = Markdown::new("# Report\n\nAll *good*.\n");
+//!let page = Page::new().stylesheet("style.css");
+//!let output = page.render(&mut Html::new(html::Flags::empty(), 0), &doc);
+//!
+//!assert_eq!(output.to_str().unwrap(), "\
+//!<!DOCTYPE html>
+//!<html>
+//!<head>
+//!<meta charset=\"utf-8\">
+//!<title>Report</title>
+//!<link rel=\"stylesheet\" href=\"style.css\">
+//!</head>
+//!<body>
+//!<h1>Report</h1>
+//!
+//!<p>All <em>good</em>.</p>
+//!</body>
+//!</html>
+//!");
+//!```
+
+use std::io::Write;
+
+use buffer::Buffer;
+use escape;
+use markdown::Markdown;
+use super::Render;
+use super::html::Html;
+
+/// The template used by default
+///
+/// See `Page::template` for the placeholders it may contain.
+pub const DEFAULT_TEMPLATE: &str = "\
+<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"{{charset}}\">
+<title>{{title}}</title>
+{{head}}</head>
+<body>
+{{toc}}{{body}}</body>
+</html>
+";
+
+/// Configuration for rendering documents as complete html pages
+#[derive(Debug, Clone)]
+pub struct Page {
+    template: String,
+    charset: String,
+    title: Option<String>,
+    stylesheets: Vec<String>,
+    scripts: Vec<String>,
+    toc_level: i32,
+}
+
+/// The inner html of the first header in the rendered html, without its tags
+fn first_header(html: &[u8]) -> Option<Vec<u8>> {
+    let start = html.windows(3).position(|w| w[0] == b'<' && w[1] == b'h' && w[2] >= b'1' && w[2] <= b'6');
+
+    start.and_then(|start| {
+        let closing = [b'<', b'/', b'h', html[start + 2]];
+
+        let content = match html[start ..].iter().position(|&c| c == b'>') {
+            Some(end) => start + end + 1,
+            None => return None,
+        };
+
+        html[content ..].windows(4).position(|w| w == closing).map(|end| {
+            let mut text = vec![];
+            let mut in_tag = false;
+
+            for &c in &html[content .. content + end] {
+                match c {
+                    b'<' => in_tag = true,
+                    b'>' => in_tag = false,
+                    _ if !in_tag => text.push(c),
+                    _ => (),
+                }
+            }
+
+            text
+        })
+    })
+}
+
+impl Page {
+    /// Construct a page configuration with the default template and a utf-8 charset
+    pub fn new() -> Page {
+        Page {
+            template: String::from(DEFAULT_TEMPLATE),
+            charset: String::from("utf-8"),
+            title: None,
+            stylesheets: vec![],
+            scripts: vec![],
+            toc_level: 0,
+        }
+    }
+
+    /// Builder method to specify the template
+    ///
+    /// The template's placeholders are replaced as follows:
+    ///
+    /// * `{{title}}`: the page's title
+    /// * `{{charset}}`: the page's charset
+    /// * `{{head}}`: the stylesheet and script tags
+    /// * `{{toc}}`: the table of contents, if any
+    /// * `{{body}}`: the rendered document
+    pub fn template<S>(mut self, template: S) -> Page
+    where S: Into<String> {
+        self.template = template.into();
+        self
+    }
+
+    /// Builder method to specify the charset
+    pub fn charset<S>(mut self, charset: S) -> Page
+    where S: Into<String> {
+        self.charset = charset.into();
+        self
+    }
+
+    /// Builder method to specify the title
+    ///
+    /// By default the title is taken from the `title` of the document's front
+    /// matter, if any, or otherwise from its first header.
+    pub fn title<S>(mut self, title: S) -> Page
+    where S: Into<String> {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Builder method to add a stylesheet link
+    pub fn stylesheet<S>(mut self, href: S) -> Page
+    where S: Into<String> {
+        self.stylesheets.push(href.into());
+        self
+    }
+
+    /// Builder method to add a script
+    pub fn script<S>(mut self, src: S) -> Page
+    where S: Into<String> {
+        self.scripts.push(src.into());
+        self
+    }
+
+    /// Builder method to embed a table of contents of headers up to the given level
+    ///
+    /// The headers only link to the table of contents if the document is
+    /// rendered by an `Html` renderer with the same nesting level.
+    pub fn toc(mut self, nesting_level: i32) -> Page {
+        self.toc_level = nesting_level;
+        self
+    }
+
+    /// Render the document as a page, using the given renderer for its body
+    ///
+    /// Any front matter is removed from the document before it's rendered.
+    pub fn render<R>(&self, renderer: &mut R, doc: &Markdown) -> Buffer
+    where R: Render {
+        let (front, doc) = doc.clone().split_front_matter();
+        let body = renderer.render(&doc);
+
+        let mut title = Buffer::new(64);
+
+        match self.title.clone().or_else(|| front.and_then(|front| front.title())) {
+            Some(text) => escape::html(text.as_bytes(), &mut title, false),
+            None => title.write_all(&first_header(&body).unwrap_or(vec![])).unwrap(),
+        }
+
+        let mut head = Buffer::new(64);
+
+        for href in &self.stylesheets {
+            head.write_all(b"<link rel=\"stylesheet\" href=\"").unwrap();
+            escape::html(href.as_bytes(), &mut head, false);
+            head.write_all(b"\">\n").unwrap();
+        }
+
+        for src in &self.scripts {
+            head.write_all(b"<script src=\"").unwrap();
+            escape::html(src.as_bytes(), &mut head, false);
+            head.write_all(b"\"></script>\n").unwrap();
+        }
+
+        let mut toc = Buffer::new(64);
+
+        if self.toc_level > 0 {
+            let contents = Html::toc(self.toc_level).render(&doc);
+
+            if !contents.is_empty() {
+                toc.write_all(b"<nav class=\"toc\">\n").unwrap();
+                toc.pipe(&contents);
+                toc.write_all(b"</nav>\n").unwrap();
+            }
+        }
+
+        let mut charset = Buffer::new(16);
+        escape::html(self.charset.as_bytes(), &mut charset, false);
+
+        let mut output = Buffer::new(64);
+        let mut rest = &self.template[..];
+
+        // replace each placeholder exactly once, so none are found in the replacements
+        while let Some(start) = rest.find("{{") {
+            let end = match rest[start ..].find("}}") {
+                Some(end) => start + end + 2,
+                None => break,
+            };
+
+            let replacement = match &rest[start + 2 .. end - 2] {
+                "title" => &title,
+                "charset" => &charset,
+                "head" => &head,
+                "toc" => &toc,
+                "body" => &body,
+                _ => {
+                    output.write_all(rest[.. end].as_bytes()).unwrap();
+                    rest = &rest[end ..];
+                    continue;
+                },
+            };
+
+            output.write_all(rest[.. start].as_bytes()).unwrap();
+            output.pipe(replacement);
+            rest = &rest[end ..];
+        }
+
+        output.write_all(rest.as_bytes()).unwrap();
+        output
+    }
+}
+
+impl Default for Page {
+    fn default() -> Page {
+        Page::new()
+    }
+}
@@ -11,62 +11,78 @@ pub struct Trace;
 #[allow(unused_variables)]
 impl Render for Trace {
     fn code_block(&mut self, output: &mut Buffer, text: Option<&Buffer>, lang: Option<&Buffer>) {
-        output.write(b"MISSING CODE_BLOCK HANDLER\n").unwrap();
+        output.write_all(b"MISSING CODE_BLOCK HANDLER\n").unwrap();
     }
 
     fn quote_block(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
-        output.write(b"MISSING QUOTE_BLOCK HANDLER\n").unwrap();
+        output.write_all(b"MISSING QUOTE_BLOCK HANDLER\n").unwrap();
     }
 
     fn header(&mut self, output: &mut Buffer, content: Option<&Buffer>, level: i32) {
-        output.write(b"MISSING HEADER HANDLER\n").unwrap();
+        output.write_all(b"MISSING HEADER HANDLER\n").unwrap();
     }
 
     fn horizontal_rule(&mut self, output: &mut Buffer) {
-        output.write(b"MISSING HORIZONTAL_RULE HANDLER\n").unwrap();
+        output.write_all(b"MISSING HORIZONTAL_RULE HANDLER\n").unwrap();
     }
 
     fn list(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: ::renderer::list::List) {
-        output.write(b"MISSING LIST HANDLER\n").unwrap();
+        output.write_all(b"MISSING LIST HANDLER\n").unwrap();
     }
 
     fn list_item(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: ::renderer::list::List) {
-        output.write(b"MISSING LIST_ITEM HANDLER\n").unwrap();
+        output.write_all(b"MISSING LIST_ITEM HANDLER\n").unwrap();
     }
 
     fn paragraph(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
-        output.write(b"MISSING PARAGRAPH HANDLER\n").unwrap();
+        output.write_all(b"MISSING PARAGRAPH HANDLER\n").unwrap();
     }
 
     fn table(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
-        output.write(b"MISSING TABLE HANDLER\n").unwrap();
+        output.write_all(b"MISSING TABLE HANDLER\n").unwrap();
     }
 
     fn table_header(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
-        output.write(b"MISSING TABLE_HEADER HANDLER\n").unwrap();
+        output.write_all(b"MISSING TABLE_HEADER HANDLER\n").unwrap();
     }
 
     fn table_body(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
-        output.write(b"MISSING TABLE_BODY HANDLER\n").unwrap();
+        output.write_all(b"MISSING TABLE_BODY HANDLER\n").unwrap();
     }
 
-    fn table_row(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
-        output.write(b"MISSING TABLE_ROW HANDLER\n").unwrap();
+    fn table_row(&mut self, output: &mut Buffer, content: Option<&Buffer>, columns: usize) {
+        output.write_all(b"MISSING TABLE_ROW HANDLER\n").unwrap();
     }
 
-    fn table_cell(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: ::renderer::Table) {
-        output.write(b"MISSING TABLE_CELL HANDLER\n").unwrap();
+    fn table_cell(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: ::renderer::Table, column: usize, columns: usize) {
+        output.write_all(b"MISSING TABLE_CELL HANDLER\n").unwrap();
     }
 
     fn footnotes(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
-        output.write(b"MISSING FOOTNOTES HANDLER\n").unwrap();
+        output.write_all(b"MISSING FOOTNOTES HANDLER\n").unwrap();
     }
 
     fn footnote_definition(&mut self, output: &mut Buffer, content: Option<&Buffer>, num: u32) {
-        output.write(b"MISSING FOOTNOTE_DEFINITION HANDLER\n").unwrap();
+        output.write_all(b"MISSING FOOTNOTE_DEFINITION HANDLER\n").unwrap();
     }
 
     fn html_block(&mut self, output: &mut Buffer, text: Option<&Buffer>) {
-        output.write(b"MISSING HTML_BLOCK HANDLER\n").unwrap();
+        output.write_all(b"MISSING HTML_BLOCK HANDLER\n").unwrap();
+    }
+
+    fn definition_list(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        output.write_all(b"MISSING DEFINITION_LIST HANDLER\n").unwrap();
+    }
+
+    fn definition_term(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        output.write_all(b"MISSING DEFINITION_TERM HANDLER\n").unwrap();
+    }
+
+    fn definition_data(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        output.write_all(b"MISSING DEFINITION_DATA HANDLER\n").unwrap();
+    }
+
+    fn admonition(&mut self, output: &mut Buffer, content: Option<&Buffer>, kind: &Buffer, title: Option<&Buffer>) {
+        output.write_all(b"MISSING ADMONITION HANDLER\n").unwrap();
     }
 }
@@ -1,5 +1,5 @@
 use buffer::Buffer;
-use super::{Render, AutoLink, Table, list};
+use super::{Render, Attributes, SourcePosition, AutoLink, Table, list};
 
 pub trait Wrapper {
     type Base: Render;
@@ -21,6 +21,11 @@ pub trait Wrapper {
         self.base().header(ob, content, level);
     }
 
+    #[inline(always)]
+    fn header_with_attributes(&mut self, ob: &mut Buffer, content: Option<&Buffer>, level: i32, attributes: &Attributes) {
+        self.base().header_with_attributes(ob, content, level, attributes);
+    }
+
     #[inline(always)]
     fn horizontal_rule(&mut self, ob: &mut Buffer) {
         self.base().horizontal_rule(ob);
@@ -57,13 +62,13 @@ pub trait Wrapper {
     }
 
     #[inline(always)]
-    fn table_row(&mut self, ob: &mut Buffer, content: Option<&Buffer>) {
-        self.base().table_row(ob, content);
+    fn table_row(&mut self, ob: &mut Buffer, content: Option<&Buffer>, columns: usize) {
+        self.base().table_row(ob, content, columns);
     }
 
     #[inline(always)]
-    fn table_cell(&mut self, ob: &mut Buffer, content: Option<&Buffer>, flags: Table) {
-        self.base().table_cell(ob, content, flags);
+    fn table_cell(&mut self, ob: &mut Buffer, content: Option<&Buffer>, flags: Table, column: usize, columns: usize) {
+        self.base().table_cell(ob, content, flags, column, columns);
     }
 
     #[inline(always)]
@@ -81,6 +86,31 @@ pub trait Wrapper {
         self.base().html_block(ob, text);
     }
 
+    #[inline(always)]
+    fn positioned_block(&mut self, ob: &mut Buffer, content: Option<&Buffer>, position: &SourcePosition) {
+        self.base().positioned_block(ob, content, position);
+    }
+
+    #[inline(always)]
+    fn definition_list(&mut self, ob: &mut Buffer, content: Option<&Buffer>) {
+        self.base().definition_list(ob, content);
+    }
+
+    #[inline(always)]
+    fn definition_term(&mut self, ob: &mut Buffer, content: Option<&Buffer>) {
+        self.base().definition_term(ob, content);
+    }
+
+    #[inline(always)]
+    fn definition_data(&mut self, ob: &mut Buffer, content: Option<&Buffer>) {
+        self.base().definition_data(ob, content);
+    }
+
+    #[inline(always)]
+    fn admonition(&mut self, ob: &mut Buffer, content: Option<&Buffer>, kind: &Buffer, title: Option<&Buffer>) {
+        self.base().admonition(ob, content, kind, title);
+    }
+
     #[inline(always)]
     fn autolink(&mut self, ob: &mut Buffer, link: Option<&Buffer>, ty: AutoLink) -> bool {
         self.base().autolink(ob, link, ty)
@@ -131,6 +161,16 @@ pub trait Wrapper {
         self.base().link(ob, content, link, title)
     }
 
+    #[inline(always)]
+    fn wiki_link(&mut self, ob: &mut Buffer, target: &Buffer, label: Option<&Buffer>) -> bool {
+        self.base().wiki_link(ob, target, label)
+    }
+
+    #[inline(always)]
+    fn pattern_link(&mut self, ob: &mut Buffer, text: &Buffer, link: &Buffer, name: &str) -> bool {
+        self.base().pattern_link(ob, text, link, name)
+    }
+
     #[inline(always)]
     fn triple_emphasis(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
         self.base().triple_emphasis(ob, content)
@@ -182,157 +222,312 @@ pub trait Wrapper {
     }
 }
 
+/// The base of a type deriving `Render`, which its `overrides` block and
+/// `Dispatch` use
+#[doc(hidden)]
+pub trait WrapperBase {
+    type Base: Render;
+
+    fn base_field(&mut self) -> &mut Self::Base;
+}
+
+/// The receiver of a derived `Render` callback, which goes to the type's
+/// `Wrapper` implementation if it has an `overrides` block, and to its base
+/// otherwise
+///
+/// `ViaWrapper` is implemented for `Dispatch` itself and `ViaBase` for a
+/// reference to it, so method resolution prefers `ViaWrapper` when it applies.
+#[doc(hidden)]
+pub struct Dispatch<'a, T: 'a>(pub &'a mut T);
+
+macro_rules! dispatch {
+    ($(fn $name:ident($($arg:ident: $ty:ty),*) $(-> $ret:ty)*;)+) => {
+        #[doc(hidden)]
+        pub trait ViaWrapper {
+            $(fn $name(self, output: &mut Buffer, $($arg: $ty),*) $(-> $ret)*;)+
+        }
+
+        impl<'a, T> ViaWrapper for Dispatch<'a, T> where T: Wrapper {
+            $(
+                #[inline(always)]
+                fn $name(self, output: &mut Buffer, $($arg: $ty),*) $(-> $ret)* {
+                    Wrapper::$name(self.0, output, $($arg),*)
+                }
+            )+
+        }
+
+        #[doc(hidden)]
+        pub trait ViaBase {
+            $(fn $name(self, output: &mut Buffer, $($arg: $ty),*) $(-> $ret)*;)+
+        }
+
+        impl<'a, 'b, T> ViaBase for &'b mut Dispatch<'a, T> where T: WrapperBase {
+            $(
+                #[inline(always)]
+                fn $name(self, output: &mut Buffer, $($arg: $ty),*) $(-> $ret)* {
+                    self.0.base_field().$name(output, $($arg),*)
+                }
+            )+
+        }
+    }
+}
+
+dispatch! {
+    fn code_block(code: Option<&Buffer>, lang: Option<&Buffer>);
+    fn quote_block(content: Option<&Buffer>);
+    fn header(content: Option<&Buffer>, level: i32);
+    fn header_with_attributes(content: Option<&Buffer>, level: i32, attributes: &Attributes);
+    fn horizontal_rule();
+    fn list(content: Option<&Buffer>, flags: list::List);
+    fn list_item(content: Option<&Buffer>, flags: list::List);
+    fn paragraph(content: Option<&Buffer>);
+    fn table(content: Option<&Buffer>);
+    fn table_header(content: Option<&Buffer>);
+    fn table_body(content: Option<&Buffer>);
+    fn table_row(content: Option<&Buffer>, columns: usize);
+    fn table_cell(content: Option<&Buffer>, flags: Table, column: usize, columns: usize);
+    fn footnotes(content: Option<&Buffer>);
+    fn footnote_definition(content: Option<&Buffer>, num: u32);
+    fn html_block(text: Option<&Buffer>);
+    fn positioned_block(content: Option<&Buffer>, position: &SourcePosition);
+    fn definition_list(content: Option<&Buffer>);
+    fn definition_term(content: Option<&Buffer>);
+    fn definition_data(content: Option<&Buffer>);
+    fn admonition(content: Option<&Buffer>, kind: &Buffer, title: Option<&Buffer>);
+    fn autolink(link: Option<&Buffer>, ty: AutoLink) -> bool;
+    fn code_span(text: Option<&Buffer>) -> bool;
+    fn double_emphasis(content: Option<&Buffer>) -> bool;
+    fn emphasis(content: Option<&Buffer>) -> bool;
+    fn underline(content: Option<&Buffer>) -> bool;
+    fn highlight(content: Option<&Buffer>) -> bool;
+    fn quote_span(content: Option<&Buffer>) -> bool;
+    fn image(link: Option<&Buffer>, title: Option<&Buffer>, alt: Option<&Buffer>) -> bool;
+    fn line_break() -> bool;
+    fn link(content: Option<&Buffer>, link: Option<&Buffer>, title: Option<&Buffer>) -> bool;
+    fn wiki_link(target: &Buffer, label: Option<&Buffer>) -> bool;
+    fn pattern_link(text: &Buffer, link: &Buffer, name: &str) -> bool;
+    fn triple_emphasis(content: Option<&Buffer>) -> bool;
+    fn strikethrough(content: Option<&Buffer>) -> bool;
+    fn superscript(content: Option<&Buffer>) -> bool;
+    fn footnote_reference(num: u32) -> bool;
+    fn math(text: Option<&Buffer>, displaymode: i32) -> bool;
+    fn html_span(text: Option<&Buffer>) -> bool;
+    fn entity(text: Option<&Buffer>);
+    fn normal_text(text: Option<&Buffer>);
+    fn before_render(inline_render: bool);
+    fn after_render(inline_render: bool);
+}
+
 #[macro_export]
 macro_rules! wrap {
-    ($name:ty) => {
-        impl $crate::renderer::Render for $name {
+    (@wrapper $method:ident($this:expr $(, $arg:expr)*)) => {
+        $crate::renderer::wrapper::Wrapper::$method($this $(, $arg)*)
+    };
+
+    // a derived type without an `overrides` block has no `Wrapper` impl
+    (@derived $method:ident($this:expr $(, $arg:expr)*)) => {{
+        #[allow(unused_imports)]
+        use $crate::renderer::wrapper::{ViaWrapper, ViaBase};
+        $crate::renderer::wrapper::Dispatch($this).$method($($arg),*)
+    }};
+
+    (@impl $via:ident [$($generics:tt)*] $name:ty where [$($bounds:tt)*]) => {
+        impl<$($generics)*> $crate::renderer::Render for $name where $($bounds)* {
             // block-level: not registered = skip the block
             #[inline(always)]
             fn code_block(&mut self, output: &mut $crate::Buffer, text: Option<&$crate::Buffer>, lang: Option<&$crate::Buffer>) {
-                $crate::renderer::wrapper::Wrapper::code_block(self, output, text, lang)
+                $crate::wrap!(@$via code_block(self, output, text, lang))
             }
             #[inline(always)]
             fn quote_block(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>) {
-                $crate::renderer::wrapper::Wrapper::quote_block(self, output, content)
+                $crate::wrap!(@$via quote_block(self, output, content))
             }
             #[inline(always)]
             fn header(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>, level: i32) {
-                $crate::renderer::wrapper::Wrapper::header(self, output, content, level)
+                $crate::wrap!(@$via header(self, output, content, level))
+            }
+            #[inline(always)]
+            fn header_with_attributes(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>, level: i32, attributes: &$crate::renderer::Attributes) {
+                $crate::wrap!(@$via header_with_attributes(self, output, content, level, attributes))
             }
             #[inline(always)]
             fn horizontal_rule(&mut self, output: &mut $crate::Buffer) {
-                $crate::renderer::wrapper::Wrapper::horizontal_rule(self, output)
+                $crate::wrap!(@$via horizontal_rule(self, output))
             }
             #[inline(always)]
             fn list(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>, flags: $crate::renderer::list::List) {
-                $crate::renderer::wrapper::Wrapper::list(self, output, content, flags)
+                $crate::wrap!(@$via list(self, output, content, flags))
             }
             #[inline(always)]
             fn list_item(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>, flags: $crate::renderer::list::List) {
-                $crate::renderer::wrapper::Wrapper::list_item(self, output, content, flags)
+                $crate::wrap!(@$via list_item(self, output, content, flags))
             }
             #[inline(always)]
             fn paragraph(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>) {
-                $crate::renderer::wrapper::Wrapper::paragraph(self, output, content)
+                $crate::wrap!(@$via paragraph(self, output, content))
             }
             #[inline(always)]
             fn table(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>) {
-                $crate::renderer::wrapper::Wrapper::table(self, output, content)
+                $crate::wrap!(@$via table(self, output, content))
             }
             #[inline(always)]
             fn table_header(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>) {
-                $crate::renderer::wrapper::Wrapper::table_header(self, output, content)
+                $crate::wrap!(@$via table_header(self, output, content))
             }
             #[inline(always)]
             fn table_body(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>) {
-                $crate::renderer::wrapper::Wrapper::table_body(self, output, content)
+                $crate::wrap!(@$via table_body(self, output, content))
             }
             #[inline(always)]
-            fn table_row(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>) {
-                $crate::renderer::wrapper::Wrapper::table_row(self, output, content)
+            fn table_row(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>, columns: usize) {
+                $crate::wrap!(@$via table_row(self, output, content, columns))
             }
             #[inline(always)]
-            fn table_cell(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>, flags: $crate::renderer::Table) {
-                $crate::renderer::wrapper::Wrapper::table_cell(self, output, content, flags)
+            fn table_cell(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>, flags: $crate::renderer::Table, column: usize, columns: usize) {
+                $crate::wrap!(@$via table_cell(self, output, content, flags, column, columns))
             }
             #[inline(always)]
             fn footnotes(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>) {
-                $crate::renderer::wrapper::Wrapper::footnotes(self, output, content)
+                $crate::wrap!(@$via footnotes(self, output, content))
             }
             #[inline(always)]
             fn footnote_definition(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>, num: u32) {
-                $crate::renderer::wrapper::Wrapper::footnote_definition(self, output, content, num)
+                $crate::wrap!(@$via footnote_definition(self, output, content, num))
             }
             #[inline(always)]
             fn html_block(&mut self, output: &mut $crate::Buffer, text: Option<&$crate::Buffer>) {
-                $crate::renderer::wrapper::Wrapper::html_block(self, output, text)
+                $crate::wrap!(@$via html_block(self, output, text))
+            }
+            #[inline(always)]
+            fn positioned_block(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>, position: &$crate::renderer::SourcePosition) {
+                $crate::wrap!(@$via positioned_block(self, output, content, position))
+            }
+            #[inline(always)]
+            fn definition_list(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>) {
+                $crate::wrap!(@$via definition_list(self, output, content))
+            }
+            #[inline(always)]
+            fn definition_term(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>) {
+                $crate::wrap!(@$via definition_term(self, output, content))
+            }
+            #[inline(always)]
+            fn definition_data(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>) {
+                $crate::wrap!(@$via definition_data(self, output, content))
+            }
+            #[inline(always)]
+            fn admonition(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>, kind: &$crate::Buffer, title: Option<&$crate::Buffer>) {
+                $crate::wrap!(@$via admonition(self, output, content, kind, title))
             }
 
             // span-level: not registered = pass-through
             #[inline(always)]
             fn autolink(&mut self, output: &mut $crate::Buffer, link: Option<&$crate::Buffer>, link_type: $crate::renderer::AutoLink) -> bool {
-                $crate::renderer::wrapper::Wrapper::autolink(self, output, link, link_type)
+                $crate::wrap!(@$via autolink(self, output, link, link_type))
             }
             #[inline(always)]
             fn code_span(&mut self, output: &mut $crate::Buffer, text: Option<&$crate::Buffer>) -> bool {
-                $crate::renderer::wrapper::Wrapper::code_span(self, output, text)
+                $crate::wrap!(@$via code_span(self, output, text))
             }
             #[inline(always)]
             fn double_emphasis(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>) -> bool {
-                $crate::renderer::wrapper::Wrapper::double_emphasis(self, output, content)
+                $crate::wrap!(@$via double_emphasis(self, output, content))
             }
             #[inline(always)]
             fn emphasis(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>) -> bool {
-                $crate::renderer::wrapper::Wrapper::emphasis(self, output, content)
+                $crate::wrap!(@$via emphasis(self, output, content))
             }
             #[inline(always)]
             fn underline(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>) -> bool {
-                $crate::renderer::wrapper::Wrapper::underline(self, output, content)
+                $crate::wrap!(@$via underline(self, output, content))
             }
             #[inline(always)]
             fn highlight(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>) -> bool {
-                $crate::renderer::wrapper::Wrapper::highlight(self, output, content)
+                $crate::wrap!(@$via highlight(self, output, content))
             }
             #[inline(always)]
             fn quote_span(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>) -> bool {
-                $crate::renderer::wrapper::Wrapper::quote_span(self, output, content)
+                $crate::wrap!(@$via quote_span(self, output, content))
             }
             #[inline(always)]
             fn image(&mut self, output: &mut $crate::Buffer, link: Option<&$crate::Buffer>, title: Option<&$crate::Buffer>, alt: Option<&$crate::Buffer>) -> bool {
-                $crate::renderer::wrapper::Wrapper::image(self, output, link, title, alt)
+                $crate::wrap!(@$via image(self, output, link, title, alt))
             }
             #[inline(always)]
             fn line_break(&mut self, output: &mut $crate::Buffer) -> bool {
-                $crate::renderer::wrapper::Wrapper::line_break(self, output)
+                $crate::wrap!(@$via line_break(self, output))
             }
             #[inline(always)]
             fn link(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>, link: Option<&$crate::Buffer>, title: Option<&$crate::Buffer>) -> bool {
-                $crate::renderer::wrapper::Wrapper::link(self, output, content, link, title)
+                $crate::wrap!(@$via link(self, output, content, link, title))
+            }
+            #[inline(always)]
+            fn wiki_link(&mut self, output: &mut $crate::Buffer, target: &$crate::Buffer, label: Option<&$crate::Buffer>) -> bool {
+                $crate::wrap!(@$via wiki_link(self, output, target, label))
+            }
+            #[inline(always)]
+            fn pattern_link(&mut self, output: &mut $crate::Buffer, text: &$crate::Buffer, link: &$crate::Buffer, name: &str) -> bool {
+                $crate::wrap!(@$via pattern_link(self, output, text, link, name))
             }
             #[inline(always)]
             fn triple_emphasis(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>) -> bool {
-                $crate::renderer::wrapper::Wrapper::triple_emphasis(self, output, content)
+                $crate::wrap!(@$via triple_emphasis(self, output, content))
             }
             #[inline(always)]
             fn strikethrough(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>) -> bool {
-                $crate::renderer::wrapper::Wrapper::strikethrough(self, output, content)
+                $crate::wrap!(@$via strikethrough(self, output, content))
             }
             #[inline(always)]
             fn superscript(&mut self, output: &mut $crate::Buffer, content: Option<&$crate::Buffer>) -> bool {
-                $crate::renderer::wrapper::Wrapper::superscript(self, output, content)
+                $crate::wrap!(@$via superscript(self, output, content))
             }
             #[inline(always)]
             fn footnote_reference(&mut self, output: &mut $crate::Buffer, num: u32) -> bool {
-                $crate::renderer::wrapper::Wrapper::footnote_reference(self, output, num)
+                $crate::wrap!(@$via footnote_reference(self, output, num))
             }
             #[inline(always)]
             fn math(&mut self, output: &mut $crate::Buffer, text: Option<&$crate::Buffer>, displaymode: i32) -> bool {
-                $crate::renderer::wrapper::Wrapper::math(self, output, text, displaymode)
+                $crate::wrap!(@$via math(self, output, text, displaymode))
             }
             #[inline(always)]
             fn html_span(&mut self, output: &mut $crate::Buffer, text: Option<&$crate::Buffer>) -> bool {
-                $crate::renderer::wrapper::Wrapper::html_span(self, output, text)
+                $crate::wrap!(@$via html_span(self, output, text))
             }
 
             // low-level: not registered = pass-through
             #[inline(always)]
             fn entity(&mut self, output: &mut $crate::Buffer, text: Option<&$crate::Buffer>) {
-                $crate::renderer::wrapper::Wrapper::entity(self, output, text)
+                $crate::wrap!(@$via entity(self, output, text))
             }
             #[inline(always)]
             fn normal_text(&mut self, output: &mut $crate::Buffer, text: Option<&$crate::Buffer>) {
-                $crate::renderer::wrapper::Wrapper::normal_text(self, output, text)
+                $crate::wrap!(@$via normal_text(self, output, text))
             }
 
             // misc callbacks
             #[inline(always)]
             fn before_render(&mut self, output: &mut $crate::Buffer, inline_render: bool) {
-                $crate::renderer::wrapper::Wrapper::before_render(self, output, inline_render)
+                $crate::wrap!(@$via before_render(self, output, inline_render))
             }
             #[inline(always)]
             fn after_render(&mut self, output: &mut $crate::Buffer, inline_render: bool) {
-                $crate::renderer::wrapper::Wrapper::after_render(self, output, inline_render)
+                $crate::wrap!(@$via after_render(self, output, inline_render))
             }
         }
     };
+
+    (@derived [$($generics:tt)*] $name:ty where [$($bounds:tt)*]) => {
+        $crate::wrap!(@impl derived [$($generics)*] $name where [$($bounds)*]);
+    };
+
+    ([$($generics:tt)*] $name:ty where [$($bounds:tt)*]) => {
+        $crate::wrap!(@impl wrapper [$($generics)*] $name where [$($bounds)*]);
+    };
+
+    ([$($generics:tt)*] $name:ty) => {
+        wrap!([$($generics)*] $name where []);
+    };
+
+    ($name:ty) => {
+        wrap!([] $name);
+    };
 }
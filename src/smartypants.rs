@@ -0,0 +1,457 @@
+//! Typographic punctuation
+//!
+//! Converts straight quotes into curly quotes, `--` and `---` into en and em
+//! dashes, and `...` into an ellipsis, as the `SmartyPants` wrapper around any
+//! renderer. Only the document's text is converted, so code spans, code blocks,
+//! math, html and urls are left untouched.
+//!
+//! The conventions of a language, such as its quotation marks and French
+//! spacing, are described by a `Locale`. Already rendered html can be converted
+//! with the `html` function, which is the localized equivalent of
+//! `renderer::html::smartypants`.
+//!
+//!``` rust
+//!# use hoedown::{Markdown, Render, Html};
+//!# use hoedown::renderer::html::Flags;
+//!# use hoedown::smartypants::{SmartyPants, Quotes};
+//!let doc = Markdown::new("\"Hallo\" -- `\"code\"`");
+//!let mut html = SmartyPants::new(Html::new(Flags::empty(), 0), Quotes::german());
+//!
+//!assert_eq!(
+//!    html.render(&doc).to_str().unwrap(),
+//!    "<p>\u{201E}Hallo\u{201C} \u{2013} <code>&quot;code&quot;</code></p>\n");
+//!```
+
+use std::io::Write;
+use std::str;
+
+use buffer::Buffer;
+use renderer::Render;
+use renderer::wrapper::Wrapper;
+
+/// The quotation marks used for a language
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Quotes {
+    /// The opening and closing marks of a quotation
+    pub primary: (char, char),
+
+    /// The opening and closing marks of a quotation within a quotation
+    pub secondary: (char, char),
+}
+
+impl Quotes {
+    /// “…” and ‘…’
+    pub fn english() -> Quotes {
+        Quotes { primary: ('\u{201C}', '\u{201D}'), secondary: ('\u{2018}', '\u{2019}') }
+    }
+
+    /// „…“ and ‚…‘
+    pub fn german() -> Quotes {
+        Quotes { primary: ('\u{201E}', '\u{201C}'), secondary: ('\u{201A}', '\u{2018}') }
+    }
+
+    /// «…» and ‹…›
+    pub fn french() -> Quotes {
+        Quotes { primary: ('\u{AB}', '\u{BB}'), secondary: ('\u{2039}', '\u{203A}') }
+    }
+
+    /// «…» and “…”, as used in Spanish, Italian and Portuguese
+    pub fn spanish() -> Quotes {
+        Quotes { primary: ('\u{AB}', '\u{BB}'), secondary: ('\u{201C}', '\u{201D}') }
+    }
+
+    /// «…» and „…“
+    pub fn russian() -> Quotes {
+        Quotes { primary: ('\u{AB}', '\u{BB}'), secondary: ('\u{201E}', '\u{201C}') }
+    }
+
+    /// „…” and «…»
+    pub fn polish() -> Quotes {
+        Quotes { primary: ('\u{201E}', '\u{201D}'), secondary: ('\u{AB}', '\u{BB}') }
+    }
+
+    /// ”…” and ’…’
+    pub fn swedish() -> Quotes {
+        Quotes { primary: ('\u{201D}', '\u{201D}'), secondary: ('\u{2019}', '\u{2019}') }
+    }
+
+    /// 「…」 and 『…』
+    pub fn japanese() -> Quotes {
+        Quotes { primary: ('\u{300C}', '\u{300D}'), secondary: ('\u{300E}', '\u{300F}') }
+    }
+}
+
+impl Default for Quotes {
+    fn default() -> Quotes {
+        Quotes::english()
+    }
+}
+
+/// How runs of hyphens are converted
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Dashes {
+    /// Leave hyphens alone
+    None,
+
+    /// `--` is an en dash and `---` is an em dash
+    Standard,
+
+    /// `--` is an em dash
+    Em,
+
+    /// `--` is an em dash and `---` is an en dash
+    Inverted,
+}
+
+/// The typographic conventions of a language
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Locale {
+    pub quotes: Quotes,
+
+    /// Whether a no-break space precedes `;`, `:`, `!` and `?`, and pads the
+    /// inside of primary quotation marks, as in French
+    pub french_spacing: bool,
+
+    pub dashes: Dashes,
+
+    /// Whether `...` is converted into an ellipsis
+    pub ellipsis: bool,
+}
+
+impl Locale {
+    /// The conventions of a language using the given quotation marks, with
+    /// standard dashes, ellipses and no French spacing
+    pub fn new(quotes: Quotes) -> Locale {
+        Locale {
+            quotes: quotes,
+            french_spacing: false,
+            dashes: Dashes::Standard,
+            ellipsis: true,
+        }
+    }
+
+    pub fn english() -> Locale {
+        Locale::new(Quotes::english())
+    }
+
+    pub fn german() -> Locale {
+        Locale::new(Quotes::german())
+    }
+
+    /// French quotation marks and spacing
+    pub fn french() -> Locale {
+        Locale { french_spacing: true, .. Locale::new(Quotes::french()) }
+    }
+
+    pub fn spanish() -> Locale {
+        Locale::new(Quotes::spanish())
+    }
+
+    pub fn russian() -> Locale {
+        Locale::new(Quotes::russian())
+    }
+
+    pub fn polish() -> Locale {
+        Locale::new(Quotes::polish())
+    }
+
+    pub fn swedish() -> Locale {
+        Locale::new(Quotes::swedish())
+    }
+
+    pub fn japanese() -> Locale {
+        Locale::new(Quotes::japanese())
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Locale {
+        Locale::english()
+    }
+}
+
+const APOSTROPHE: char = '\u{2019}';
+const EN_DASH: char = '\u{2013}';
+const EM_DASH: char = '\u{2014}';
+const ELLIPSIS: char = '\u{2026}';
+const NO_BREAK_SPACE: char = '\u{A0}';
+const NARROW_NO_BREAK_SPACE: char = '\u{202F}';
+
+/// Whether a quote following the character opens a quotation
+fn opens_after(previous: Option<char>, quotes: &Quotes) -> bool {
+    match previous {
+        None => true,
+        Some(c) => {
+            c.is_whitespace()
+                || "([{-\u{2013}\u{2014}".contains(c)
+                || c == quotes.primary.0
+                || c == quotes.secondary.0
+        },
+    }
+}
+
+/// Whether French spacing applies before the punctuation, given the character
+/// that follows it
+///
+/// e.g. the colons in `10:30` and `http://` aren't spaced.
+fn is_spaced(c: char, next: Option<char>) -> bool {
+    ";:!?".contains(c) && next.map_or(true, |n| n.is_whitespace() || ";:!?)\"'\u{BB}\u{201D}\u{2019}".contains(n))
+}
+
+/// Convert the punctuation of the text, given the character that precedes it
+pub(crate) fn educate(text: &str, previous: Option<char>, locale: &Locale) -> String {
+    let quotes = &locale.quotes;
+    let chars: Vec<char> = text.chars().collect();
+    let mut output = String::with_capacity(text.len());
+    let mut previous = previous;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).cloned();
+        let run = chars[i ..].iter().take_while(|&&d| d == c).count();
+
+        // longer runs of dashes or dots are left alone
+        if (c == '-' || c == '.') && run > 3 {
+            output.extend(&chars[i .. i + run]);
+            previous = Some(c);
+            i += run;
+            continue;
+        }
+
+        if locale.french_spacing && (is_spaced(c, next) || (c == '"' && !opens_after(previous, quotes))) {
+            // replace a plain space, or add a space after anything but the start of the text
+            if output.ends_with(' ') {
+                output.pop();
+                previous = Some(' ');
+            }
+
+            if previous.map_or(false, |p| p != '\n') && !output.ends_with(NO_BREAK_SPACE) {
+                output.push(if c == ':' || c == '"' { NO_BREAK_SPACE } else { NARROW_NO_BREAK_SPACE });
+            }
+        }
+
+        let educated = match (c, locale.dashes) {
+            ('-', Dashes::Standard) if run == 3 => { i += 2; EM_DASH },
+            ('-', Dashes::Standard) if run == 2 => { i += 1; EN_DASH },
+            ('-', Dashes::Em) if run == 2 => { i += 1; EM_DASH },
+            ('-', Dashes::Inverted) if run == 3 => { i += 2; EN_DASH },
+            ('-', Dashes::Inverted) if run == 2 => { i += 1; EM_DASH },
+            ('-', _) => {
+                // leave the whole run as it is
+                output.extend(&chars[i .. i + run]);
+                previous = Some(c);
+                i += run;
+                continue;
+            },
+            _ => c,
+        };
+
+        let educated = match educated {
+            '.' if run == 3 && locale.ellipsis => { i += 2; ELLIPSIS },
+            '"' if opens_after(previous, quotes) => quotes.primary.0,
+            '"' => quotes.primary.1,
+            '\'' => {
+                let after_word = previous.map_or(false, |p| p.is_alphanumeric());
+
+                if after_word && next.map_or(false, |n| n.is_alphanumeric()) {
+                    // a contraction, e.g. don't
+                    APOSTROPHE
+                } else if opens_after(previous, quotes) && next.map_or(false, |n| n.is_numeric()) {
+                    // an abbreviated year, e.g. '90s
+                    APOSTROPHE
+                } else if opens_after(previous, quotes) {
+                    quotes.secondary.0
+                } else {
+                    quotes.secondary.1
+                }
+            },
+            educated => educated,
+        };
+
+        output.push(educated);
+        previous = Some(educated);
+        i += 1;
+
+        if locale.french_spacing && educated == quotes.primary.0 && c == '"' {
+            // pad the inside of the quotation, replacing a plain space
+            while chars.get(i) == Some(&' ') {
+                i += 1;
+            }
+
+            output.push(NO_BREAK_SPACE);
+            previous = Some(NO_BREAK_SPACE);
+        }
+    }
+
+    output
+}
+
+/// Elements whose contents are left alone
+const SKIPPED: &[&str] = &["pre", "code", "var", "samp", "kbd", "math", "script", "style"];
+
+/// The character that an entity stands for, as far as converting the text
+/// around it is concerned
+fn entity(entity: &str) -> char {
+    match entity {
+        "&quot;" => '"',
+        "&#39;" | "&#x27;" | "&apos;" => '\'',
+        "&nbsp;" => NO_BREAK_SPACE,
+        "&amp;" => '&',
+        "&lt;" => '<',
+        "&gt;" => '>',
+        _ => 'x',
+    }
+}
+
+/// Convert the punctuation of text within html, writing the result to the output
+///
+/// Tags and the contents of elements such as `<code>` and `<pre>` are left
+/// alone. Converted punctuation is written as utf-8 rather than as entities.
+///
+///``` rust
+///# use hoedown::Buffer;
+///# use hoedown::smartypants::{self, Locale};
+///let input = Buffer::from("<p>&quot;Vraiment?&quot; <code>&quot;non&quot;</code></p>");
+///let mut output = Buffer::new(64);
+///
+///smartypants::html(&input, &mut output, &Locale::french());
+///
+///assert_eq!(
+///    output.to_str().unwrap(),
+///    "<p>\u{AB}\u{A0}Vraiment\u{202F}?\u{A0}\u{BB} <code>&quot;non&quot;</code></p>");
+///```
+pub fn html(input: &Buffer, output: &mut Buffer, locale: &Locale) {
+    let input = String::from_utf8_lossy(input);
+    let mut previous = None;
+    let mut text = String::new();
+    let mut rest = &input[..];
+
+    let flush = |text: &mut String, previous: &mut Option<char>, output: &mut Buffer| {
+        if !text.is_empty() {
+            let educated = educate(text, *previous, locale);
+            *previous = educated.chars().last();
+            output.write_all(educated.as_bytes()).unwrap();
+            text.clear();
+        }
+    };
+
+    while !rest.is_empty() {
+        let c = rest.chars().next().unwrap();
+
+        match c {
+            '<' => {
+                flush(&mut text, &mut previous, output);
+
+                let end = rest.find('>').map_or(rest.len(), |i| i + 1);
+                let name: String =
+                    rest[1 .. end].chars()
+                    .take_while(|c| c.is_alphanumeric())
+                    .collect::<String>()
+                    .to_lowercase();
+
+                // copy skipped elements up to their closing tags
+                let end =
+                    if SKIPPED.contains(&&name[..]) {
+                        let closing = format!("</{}", name);
+                        rest.to_ascii_lowercase().find(&closing)
+                            .map_or(rest.len(), |start| start + rest[start ..].find('>').map_or(rest.len() - start, |i| i + 1))
+                    } else {
+                        end
+                    };
+
+                output.write_all(rest[.. end].as_bytes()).unwrap();
+                rest = &rest[end ..];
+            },
+            '&' => {
+                let end = match rest.find(';') {
+                    Some(end) if rest[1 .. end].chars().all(|c| c.is_alphanumeric() || c == '#') => end + 1,
+                    _ => 1,
+                };
+
+                match entity(&rest[.. end]) {
+                    quote @ '"' | quote @ '\'' => text.push(quote),
+                    c => {
+                        flush(&mut text, &mut previous, output);
+                        output.write_all(rest[.. end].as_bytes()).unwrap();
+                        previous = Some(c);
+                    },
+                }
+
+                rest = &rest[end ..];
+            },
+            c => {
+                text.push(c);
+                rest = &rest[c.len_utf8() ..];
+            },
+        }
+    }
+
+    flush(&mut text, &mut previous, output);
+}
+
+/// The last character written to the buffer, if it's valid utf-8
+fn last_char(ob: &Buffer) -> Option<char> {
+    let start = ob.len().saturating_sub(4);
+
+    (start .. ob.len())
+        .filter_map(|i| str::from_utf8(&ob[i ..]).ok())
+        .next()
+        .and_then(|s| s.chars().last())
+}
+
+/// Renderer wrapper that converts the punctuation of another renderer's text
+///
+/// The converted text is rendered by the wrapped renderer's `normal_text`.
+pub struct SmartyPants<R> {
+    base: R,
+    locale: Locale,
+}
+
+impl<R> SmartyPants<R> where R: Render {
+    /// Wrap the renderer, using the given quotation marks
+    pub fn new(base: R, quotes: Quotes) -> SmartyPants<R> {
+        SmartyPants::with_locale(base, Locale::new(quotes))
+    }
+
+    /// Wrap the renderer, following the conventions of the given locale
+    pub fn with_locale(base: R, locale: Locale) -> SmartyPants<R> {
+        SmartyPants {
+            base: base,
+            locale: locale,
+        }
+    }
+
+    /// Unwrap the renderer
+    pub fn into_inner(self) -> R {
+        self.base
+    }
+}
+
+wrap!([R: Render] SmartyPants<R>);
+
+impl<R> Wrapper for SmartyPants<R> where R: Render {
+    type Base = R;
+
+    #[inline(always)]
+    fn base(&mut self) -> &mut R {
+        &mut self.base
+    }
+
+    fn normal_text(&mut self, ob: &mut Buffer, text: Option<&Buffer>) {
+        let text = match text {
+            Some(text) => text,
+            None => return self.base.normal_text(ob, None),
+        };
+
+        match str::from_utf8(text) {
+            Ok(text) => {
+                // hoedown splits text around markup, so the quotation may have
+                // begun in the output that precedes it
+                let educated = educate(text, last_char(ob), &self.locale);
+                self.base.normal_text(ob, Some(&Buffer::from(&educated[..])))
+            },
+            Err(_) => self.base.normal_text(ob, Some(text)),
+        }
+    }
+}
@@ -0,0 +1,364 @@
+//! Table extraction
+//!
+//! Collects the tables of a document as rows of cell values, which may be
+//! written out as CSV or JSON. The `TABLES` extension is enabled for
+//! extraction even if the document doesn't enable it.
+//!
+//!``` rust
+//!# use hoedown::Markdown;
+//!# use hoedown::renderer::table::Alignment;
+//!let doc = Markdown::new("| Code | Meaning |\n|-----:|---------|\n| 404 | *Not* found |\n");
+//!let tables = doc.tables();
+//!
+//!assert_eq!(tables[0].headers, vec!["Code", "Meaning"]);
+//!assert_eq!(tables[0].alignments, vec![Alignment::Right, Alignment::None]);
+//!assert_eq!(tables[0].rows, vec![vec!["404", "Not found"]]);
+//!assert_eq!(tables[0].to_csv(), "Code,Meaning\r\n404,Not found\r\n");
+//!```
+
+use std::io::{self, Write};
+use std::mem;
+
+use buffer::Buffer;
+use extensions::TABLES;
+use markdown::Markdown;
+use renderer::{Render, AutoLink};
+use renderer::html::{self, Html};
+use renderer::table::{self, Alignment};
+use renderer::wrapper::Wrapper;
+
+/// A table found in a document
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    /// The cells of the header row
+    pub headers: Vec<String>,
+
+    /// The alignment of each column
+    pub alignments: Vec<Alignment>,
+
+    /// The cells of each row of the body
+    ///
+    /// Every row has as many cells as there are headers.
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Extract the tables from the document, with cells as plain text
+///
+/// Spans are rendered as their bare content, e.g. `*a* [b](c)` as `a b`.
+/// Numeric entities and common named ones, e.g. `&amp;`, are decoded, while
+/// other named entities are left as they are.
+pub fn extract(doc: &Markdown) -> Vec<Table> {
+    collect(doc, Text)
+}
+
+/// Extract the tables from the document, with cells as html rendered with the given flags
+pub fn extract_html(doc: &Markdown, flags: html::Flags) -> Vec<Table> {
+    collect(doc, Html::new(flags, 0))
+}
+
+fn collect<R>(doc: &Markdown, base: R) -> Vec<Table>
+where R: Render {
+    let doc = doc.clone().extensions(doc.extensions | TABLES);
+    let mut collector = Collector {
+        base: base,
+        tables: vec![],
+        table: Table::default(),
+        row: vec![],
+    };
+
+    collector.render(&doc);
+    collector.tables
+}
+
+/// Write a CSV field, quoting it if necessary
+fn write_csv_field<W>(writer: &mut W, field: &str) -> io::Result<()>
+where W: Write {
+    if field.contains(|c| c == ',' || c == '"' || c == '\n' || c == '\r') {
+        write!(writer, "\"{}\"", field.replace('"', "\"\""))
+    } else {
+        writer.write_all(field.as_bytes())
+    }
+}
+
+fn write_csv_record<W>(writer: &mut W, record: &[String]) -> io::Result<()>
+where W: Write {
+    for (i, field) in record.iter().enumerate() {
+        if i > 0 {
+            try!(writer.write_all(b","));
+        }
+
+        try!(write_csv_field(writer, field));
+    }
+
+    writer.write_all(b"\r\n")
+}
+
+/// Write a JSON string, escaping it as necessary
+fn write_json_string<W>(writer: &mut W, text: &str) -> io::Result<()>
+where W: Write {
+    try!(writer.write_all(b"\""));
+
+    for c in text.chars() {
+        match c {
+            '"' => try!(writer.write_all(b"\\\"")),
+            '\\' => try!(writer.write_all(b"\\\\")),
+            '\n' => try!(writer.write_all(b"\\n")),
+            '\r' => try!(writer.write_all(b"\\r")),
+            '\t' => try!(writer.write_all(b"\\t")),
+            c if (c as u32) < 0x20 => try!(write!(writer, "\\u{:04x}", c as u32)),
+            c => try!(write!(writer, "{}", c)),
+        }
+    }
+
+    writer.write_all(b"\"")
+}
+
+fn write_json_strings<W>(writer: &mut W, strings: &[String]) -> io::Result<()>
+where W: Write {
+    try!(writer.write_all(b"["));
+
+    for (i, string) in strings.iter().enumerate() {
+        if i > 0 {
+            try!(writer.write_all(b","));
+        }
+
+        try!(write_json_string(writer, string));
+    }
+
+    writer.write_all(b"]")
+}
+
+impl Table {
+    /// Write the table as CSV, as described by RFC 4180, beginning with the headers
+    pub fn write_csv<W>(&self, mut writer: W) -> io::Result<()>
+    where W: Write {
+        try!(write_csv_record(&mut writer, &self.headers));
+
+        for row in &self.rows {
+            try!(write_csv_record(&mut writer, row));
+        }
+
+        Ok(())
+    }
+
+    /// Write the table as a JSON object
+    ///
+    /// The object has `headers`, `alignments` and `rows` members like the
+    /// table's fields. Alignments are `"left"`, `"right"`, `"center"`, or
+    /// `null` if unspecified.
+    pub fn write_json<W>(&self, mut writer: W) -> io::Result<()>
+    where W: Write {
+        try!(writer.write_all(b"{\"headers\":"));
+        try!(write_json_strings(&mut writer, &self.headers));
+        try!(writer.write_all(b",\"alignments\":["));
+
+        for (i, alignment) in self.alignments.iter().enumerate() {
+            if i > 0 {
+                try!(writer.write_all(b","));
+            }
+
+            try!(writer.write_all(match *alignment {
+                Alignment::None => b"null",
+                Alignment::Left => b"\"left\"",
+                Alignment::Right => b"\"right\"",
+                Alignment::Center => b"\"center\"",
+            }));
+        }
+
+        try!(writer.write_all(b"],\"rows\":["));
+
+        for (i, row) in self.rows.iter().enumerate() {
+            if i > 0 {
+                try!(writer.write_all(b","));
+            }
+
+            try!(write_json_strings(&mut writer, row));
+        }
+
+        writer.write_all(b"]}")
+    }
+
+    /// The table as CSV
+    pub fn to_csv(&self) -> String {
+        let mut output = vec![];
+        self.write_csv(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    /// The table as a JSON object
+    pub fn to_json(&self) -> String {
+        let mut output = vec![];
+        self.write_json(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+}
+
+/// The character that an entity such as `&amp;` or `&#38;` stands for
+///
+/// Only numeric entities and the most common named ones are known.
+fn decode_entity(entity: &[u8]) -> Option<char> {
+    if entity.len() < 3 || entity[0] != b'&' || entity[entity.len() - 1] != b';' {
+        return None;
+    }
+
+    let name = match ::std::str::from_utf8(&entity[1 .. entity.len() - 1]) {
+        Ok(name) => name,
+        Err(_) => return None,
+    };
+
+    let code =
+        if name.starts_with("#x") || name.starts_with("#X") {
+            u32::from_str_radix(&name[2 ..], 16).ok()
+        } else if name.starts_with('#') {
+            name[1 ..].parse().ok()
+        } else {
+            None
+        };
+
+    if let Some(code) = code {
+        return ::std::char::from_u32(code);
+    }
+
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{A0}'),
+        "copy" => Some('\u{A9}'),
+        "reg" => Some('\u{AE}'),
+        "trade" => Some('\u{2122}'),
+        "ndash" => Some('\u{2013}'),
+        "mdash" => Some('\u{2014}'),
+        "hellip" => Some('\u{2026}'),
+        _ => None,
+    }
+}
+
+/// Renderer that renders spans as their bare content
+struct Text;
+
+impl Text {
+    fn span(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        content.map(|c| ob.pipe(c));
+        true
+    }
+}
+
+impl Render for Text {
+    fn autolink(&mut self, ob: &mut Buffer, link: Option<&Buffer>, _link_type: AutoLink) -> bool {
+        self.span(ob, link)
+    }
+
+    fn code_span(&mut self, ob: &mut Buffer, text: Option<&Buffer>) -> bool {
+        self.span(ob, text)
+    }
+
+    fn double_emphasis(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn emphasis(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn underline(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn highlight(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn quote_span(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn image(&mut self, ob: &mut Buffer, _link: Option<&Buffer>, _title: Option<&Buffer>, alt: Option<&Buffer>) -> bool {
+        self.span(ob, alt)
+    }
+
+    fn line_break(&mut self, ob: &mut Buffer) -> bool {
+        ob.write_all(b"\n").unwrap();
+        true
+    }
+
+    fn link(&mut self, ob: &mut Buffer, content: Option<&Buffer>, _link: Option<&Buffer>, _title: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn wiki_link(&mut self, ob: &mut Buffer, target: &Buffer, label: Option<&Buffer>) -> bool {
+        self.span(ob, label.or(Some(target)))
+    }
+
+    fn triple_emphasis(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn strikethrough(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn superscript(&mut self, ob: &mut Buffer, content: Option<&Buffer>) -> bool {
+        self.span(ob, content)
+    }
+
+    fn math(&mut self, ob: &mut Buffer, text: Option<&Buffer>, _displaymode: i32) -> bool {
+        self.span(ob, text)
+    }
+
+    fn entity(&mut self, ob: &mut Buffer, text: Option<&Buffer>) {
+        if let Some(text) = text {
+            match decode_entity(text) {
+                Some(c) => write!(ob, "{}", c).unwrap(),
+                None => ob.pipe(text),
+            }
+        }
+    }
+}
+
+/// Renderer wrapper that collects the tables, with cells rendered by its base
+struct Collector<R> {
+    base: R,
+    tables: Vec<Table>,
+
+    /// The table being parsed
+    table: Table,
+
+    /// The cells of the body row being parsed
+    row: Vec<String>,
+}
+
+wrap!([R: Render] Collector<R>);
+
+impl<R> Wrapper for Collector<R> where R: Render {
+    type Base = R;
+
+    #[inline(always)]
+    fn base(&mut self) -> &mut R {
+        &mut self.base
+    }
+
+    fn table(&mut self, _ob: &mut Buffer, _content: Option<&Buffer>) {
+        self.tables.push(mem::replace(&mut self.table, Table::default()));
+    }
+
+    fn table_row(&mut self, _ob: &mut Buffer, _content: Option<&Buffer>, _columns: usize) {
+        // the header row's cells were collected as headers
+        if !self.row.is_empty() {
+            self.table.rows.push(mem::replace(&mut self.row, vec![]));
+        }
+    }
+
+    fn table_cell(&mut self, _ob: &mut Buffer, content: Option<&Buffer>, flags: table::Table, _column: usize, _columns: usize) {
+        let text = content.map_or(String::new(), |c| String::from_utf8_lossy(c).into_owned());
+
+        if flags.is_header() {
+            self.table.headers.push(text);
+            self.table.alignments.push(flags.alignment());
+        } else {
+            self.row.push(text);
+        }
+    }
+}
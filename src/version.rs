@@ -0,0 +1,21 @@
+use libc::c_int;
+
+use ffi::hoedown_version;
+
+/// The major version of libhoedown that these bindings are written for
+///
+/// Building with the `system-lib` feature fails if the system's libhoedown
+/// has a different major version.
+pub const VERSION_MAJOR: i32 = 3;
+
+/// The version of the linked libhoedown, as `(major, minor, revision)`
+///
+/// This may differ from the vendored version if the crate was built with the
+/// `system-lib` feature.
+pub fn version() -> (i32, i32, i32) {
+    let (mut major, mut minor, mut revision): (c_int, c_int, c_int) = (0, 0, 0);
+
+    unsafe { hoedown_version(&mut major, &mut minor, &mut revision); }
+
+    (major, minor, revision)
+}
@@ -1,7 +1,18 @@
+use std::cell::RefCell;
+
 use libc::{c_void, c_int, c_uint};
 use buffer::Buffer;
 use ffi::{hoedown_buffer, hoedown_renderer};
-use renderer::Render;
+use renderer::{Render, Table};
+
+thread_local! {
+    /// The cells of the table row being parsed by each document, keyed by the
+    /// document's callback data
+    ///
+    /// hoedown doesn't report the columns of a cell, so the cells are only
+    /// passed on once their row ends and their number is known.
+    static ROWS: RefCell<Vec<(*mut c_void, Vec<(Option<Buffer>, Table)>)>> = RefCell::new(vec![]);
+}
 
 #[inline]
 fn get_renderer<'a, R>(data: &'a *mut c_void) -> &'a mut R {
@@ -22,6 +33,54 @@ fn get_renderer<'a, R>(data: &'a *mut c_void) -> &'a mut R {
     }
 }
 
+/// Construct a hoedown renderer whose callbacks are dispatched to the given renderer
+pub unsafe fn renderer<R>(renderer: &mut R) -> hoedown_renderer
+where R: Render {
+    hoedown_renderer {
+        opaque: renderer as *mut R as *mut c_void,
+
+        // block-level handlers are unconditionally registered
+        blockcode: Some(blockcode::<R>),
+        blockquote: Some(blockquote::<R>),
+        header: Some(header::<R>),
+        hrule: Some(hrule::<R>),
+        list: Some(list::<R>),
+        listitem: Some(listitem::<R>),
+        paragraph: Some(paragraph::<R>),
+        table: Some(table::<R>),
+        table_header: Some(table_header::<R>),
+        table_body: Some(table_body::<R>),
+        table_row: Some(table_row::<R>),
+        table_cell: Some(table_cell::<R>),
+        footnotes: Some(footnotes::<R>),
+        footnote_def: Some(footnote_def::<R>),
+        blockhtml: Some(blockhtml::<R>),
+
+        autolink: Some(autolink::<R>),
+        codespan: Some(codespan::<R>),
+        double_emphasis: Some(double_emphasis::<R>),
+        emphasis: Some(emphasis::<R>),
+        underline: Some(underline::<R>),
+        highlight: Some(highlight::<R>),
+        quote: Some(quote::<R>),
+        image: Some(image::<R>),
+        linebreak: Some(linebreak::<R>),
+        link: Some(link::<R>),
+        triple_emphasis: Some(triple_emphasis::<R>),
+        strikethrough: Some(strikethrough::<R>),
+        superscript: Some(superscript::<R>),
+        footnote_ref: Some(footnote_ref::<R>),
+        math: Some(math::<R>),
+        raw_html: Some(raw_html::<R>),
+
+        entity: Some(entity::<R>),
+        normal_text: Some(normal_text::<R>),
+
+        doc_header: Some(doc_header::<R>),
+        doc_footer: Some(doc_footer::<R>),
+    }
+}
+
 pub extern "C" fn blockcode<R>(ob: *mut hoedown_buffer,
                                text: *const hoedown_buffer,
                                lang: *const hoedown_buffer,
@@ -150,28 +209,48 @@ where R: Render {
 }
 
 pub extern "C" fn table_row<R>(ob: *mut hoedown_buffer,
-                               content: *const hoedown_buffer,
+                               _content: *const hoedown_buffer,
                                data: *mut c_void)
 where R: Render {
     assert!(!ob.is_null());
 
+    // the row's cells were kept instead of being rendered into its content
+
     let renderer = get_renderer::<R>(&data);
     let mut out = Buffer::from_raw_mut(ob).unwrap();
-    let content = Buffer::from_raw(content);
-    renderer.table_row(&mut out, content.as_ref());
+
+    let cells = ROWS.with(|rows| {
+        let mut rows = rows.borrow_mut();
+        rows.iter().position(|&(d, _)| d == data).map_or(vec![], |i| rows.swap_remove(i).1)
+    });
+
+    let columns = cells.len();
+    let mut content = Buffer::new(64);
+
+    for (column, (cell, flags)) in cells.into_iter().enumerate() {
+        renderer.table_cell(&mut content, cell.as_ref(), flags, column, columns);
+    }
+
+    renderer.table_row(&mut out, Some(&content), columns);
 }
 
 pub extern "C" fn table_cell<R>(ob: *mut hoedown_buffer,
                                 content: *const hoedown_buffer,
-                                flags: ::renderer::Table,
+                                flags: u32,
                                 data: *mut c_void)
 where R: Render {
     assert!(!ob.is_null());
 
-    let renderer = get_renderer::<R>(&data);
-    let mut out = Buffer::from_raw_mut(ob).unwrap();
-    let content = Buffer::from_raw(content);
-    renderer.table_cell(&mut out, content.as_ref(), flags);
+    let cell = (Buffer::from_raw(content).map(|c| Buffer::from(&c[..])), Table::from_bits_truncate(flags));
+
+    ROWS.with(|rows| {
+        let mut rows = rows.borrow_mut();
+
+        match rows.iter().position(|&(d, _)| d == data) {
+            Some(i) => rows[i].1.push(cell),
+            None => rows.push((data, vec![cell])),
+        }
+    });
 }
 
 pub extern "C" fn footnotes<R>(ob: *mut hoedown_buffer,
@@ -0,0 +1,91 @@
+extern crate hoedown;
+
+use hoedown::{Markdown, Render, Buffer, Html};
+use hoedown::autolink::{self, find_links, Patterns};
+use hoedown::renderer::AutoLink;
+use hoedown::renderer::html::Flags;
+
+fn links(text: &str) -> Vec<(&str, AutoLink)> {
+    find_links(text).map(|(range, kind)| (&text[range], kind)).collect()
+}
+
+#[test]
+fn test_find_links() {
+    assert_eq!(
+        links("see https://example.com/a?b=c, www.rust-lang.org or bob@example.org."),
+        vec![("https://example.com/a?b=c", AutoLink::Normal),
+             ("www.rust-lang.org", AutoLink::Normal),
+             ("bob@example.org", AutoLink::Email)]);
+}
+
+#[test]
+fn test_find_links_delimiters() {
+    assert_eq!(
+        links("(http://example.com/wiki/Rust_(language)) and http://example.com/x)."),
+        vec![("http://example.com/wiki/Rust_(language)", AutoLink::Normal),
+             ("http://example.com/x", AutoLink::Normal)]);
+}
+
+#[test]
+fn test_find_links_rejected() {
+    assert!(links("javascript://alert(1) or awww.example.com or user@localhost").is_empty());
+}
+
+#[test]
+fn test_linkify() {
+    assert_eq!(
+        autolink::linkify("<b> www.example.com & a@b.io"),
+        "&lt;b&gt; <a href=\"http://www.example.com\">www.example.com</a> &amp; \
+         <a href=\"mailto:a@b.io\">a@b.io</a>");
+}
+
+struct PatternRenderer;
+
+impl Render for PatternRenderer {
+    fn paragraph(&mut self, ob: &mut Buffer, content: Option<&Buffer>) {
+        content.map(|c| ob.pipe(c));
+    }
+
+    fn pattern_link(&mut self, ob: &mut Buffer, text: &Buffer, link: &Buffer, name: &str) -> bool {
+        let s = format!("[{} {} {}]", name, text.to_str().unwrap(), link.to_str().unwrap());
+        ob.pipe(&Buffer::from(&s[..]));
+        true
+    }
+}
+
+#[test]
+fn test_patterns() {
+    let doc = Markdown::new("Fixes #12 by @wendy in GH-99, see 3f2a9c1 or deadbeef and 1234567.").extensions(hoedown::AUTOLINK);
+    let mut renderer =
+        Patterns::new(PatternRenderer)
+        .issues("/issues/")
+        .mentions("/users/")
+        .keys("GH", "/browse/")
+        .commits("/commit/");
+
+    assert_eq!(
+        renderer.render(&doc).to_str().unwrap(),
+        "Fixes [issue #12 /issues/12] by [mention @wendy /users/wendy] in [key GH-99 /browse/GH-99], \
+         see [commit 3f2a9c1 /commit/3f2a9c1] or deadbeef and 1234567.");
+}
+
+#[test]
+fn test_patterns_html() {
+    let doc = Markdown::new("a#1 `#2` [see #3](/x) #4");
+    let mut html = Patterns::new(Html::new(Flags::empty(), 0)).issues("/issues/");
+
+    assert_eq!(
+        html.render(&doc).to_str().unwrap(),
+        "<p>a#1 <code>#2</code> <a href=\"/x\">see #3</a> <a href=\"/issues/4\">#4</a></p>\n");
+}
+
+#[test]
+fn test_patterns_in_links() {
+    let doc = Markdown::new("See #1 and [*#1* or #2](/x), then #2\n\n[#3](/y)");
+    let mut html = Patterns::new(Html::new(Flags::empty(), 0)).issues("/issues/");
+
+    assert_eq!(
+        html.render(&doc).to_str().unwrap(),
+        "<p>See <a href=\"/issues/1\">#1</a> and <a href=\"/x\"><em>#1</em> or #2</a>, then <a href=\"/issues/2\">#2</a></p>\n\n\
+         <p><a href=\"/y\">#3</a></p>\n");
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "derive")]
+
+extern crate hoedown;
+
+use hoedown::{Markdown, Buffer, Render, Html, overrides};
+use hoedown::renderer;
+
+use std::io::Write;
+
+struct Plain;
+
+impl Render for Plain {
+    fn normal_text(&mut self, output: &mut Buffer, text: Option<&Buffer>) {
+        text.map(|t| output.pipe(t));
+    }
+
+    fn emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+        output.write_all(b"_").unwrap();
+        content.map(|c| output.pipe(c));
+        output.write_all(b"_").unwrap();
+        true
+    }
+}
+
+#[derive(Render)]
+struct Upper<R: Render> {
+    #[render(base)]
+    base: R,
+    texts: usize,
+}
+
+#[overrides]
+impl<R: Render> Upper<R> {
+    fn normal_text(&mut self, output: &mut Buffer, text: Option<&Buffer>) {
+        self.texts += 1;
+        let upper = text.map(|t| Buffer::from(&t.to_str().unwrap().to_uppercase()[..]));
+        self.base().normal_text(output, upper.as_ref())
+    }
+}
+
+#[derive(Render)]
+struct Bare<R: Render> {
+    #[render(base)]
+    base: R,
+}
+
+#[derive(Render)]
+struct Strike(#[render(base)] Html);
+
+#[overrides]
+impl Strike {
+    fn emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+        output.write_all(b"<s>").unwrap();
+        content.map(|c| output.pipe(c));
+        output.write_all(b"</s>").unwrap();
+        true
+    }
+}
+
+#[test]
+fn test_derive_overrides() {
+    let mut upper = Upper { base: Plain, texts: 0 };
+    let mut output = Buffer::new(64);
+    let text = Buffer::from("text");
+
+    Render::normal_text(&mut upper, &mut output, Some(&text));
+    assert!(Render::emphasis(&mut upper, &mut output, Some(&text)));
+
+    assert_eq!(output.to_str().unwrap(), "TEXT_text_");
+    assert_eq!(upper.texts, 1);
+}
+
+#[test]
+fn test_derive_without_overrides() {
+    let mut bare = Bare { base: Plain };
+    let mut output = Buffer::new(64);
+    let text = Buffer::from("text");
+
+    Render::normal_text(&mut bare, &mut output, Some(&text));
+    assert!(Render::emphasis(&mut bare, &mut output, Some(&text)));
+    assert!(!Render::strikethrough(&mut bare, &mut output, Some(&text)));
+
+    assert_eq!(output.to_str().unwrap(), "text_text_");
+}
+
+#[test]
+fn test_derive_render() {
+    let doc = Markdown::new("*old* **new**\n");
+    let mut strike = Strike(Html::new(renderer::html::Flags::empty(), 0));
+
+    assert_eq!(strike.render(&doc).to_str().unwrap(), "<p><s>old</s> <strong>new</strong></p>\n");
+}
@@ -0,0 +1,101 @@
+extern crate hoedown;
+
+use hoedown::{Markdown, Render, Html};
+use hoedown::renderer::html;
+use hoedown::renderer::builder::RendererBuilder;
+use hoedown::renderer::dynamic::DynRender;
+use hoedown::emoji::{self, Emoji, Style};
+
+macro_rules! emoji_test {
+    ($left:expr, $right:expr) => ({
+        let doc = Markdown::new($left);
+        let mut renderer = Emoji::new(Html::new(html::Flags::empty(), 0), Style::Unicode);
+
+        assert_eq!(renderer.render(&doc).to_str().unwrap(), $right);
+    });
+}
+
+#[test]
+fn test_lookup() {
+    assert_eq!(emoji::lookup("+1"), Some("\u{1F44D}"));
+    assert_eq!(emoji::lookup("heart_eyes"), Some("\u{1F60D}"));
+    assert_eq!(emoji::lookup(":smile:"), None);
+}
+
+#[test]
+fn test_unicode() {
+    emoji_test!(
+        "Good job :+1: :tada:",
+        "<p>Good job \u{1F44D} \u{1F389}</p>\n");
+}
+
+#[test]
+fn test_split_text() {
+    // hoedown reports the text on either side of the `_` separately
+    emoji_test!(
+        "I :heart_eyes: it",
+        "<p>I \u{1F60D} it</p>\n");
+}
+
+#[test]
+fn test_split_by_markup() {
+    // the html span renders nothing, but still separates the text around it
+    let doc = Markdown::new("I :heart<br>_eyes: it");
+    let base =
+        RendererBuilder::new(Html::new(html::Flags::empty(), 0))
+        .on_html_span(|_, _| true)
+        .build();
+    let mut renderer = Emoji::new(base, Style::Unicode);
+
+    assert_eq!(renderer.render(&doc).to_str().unwrap(), "<p>I :heart_eyes: it</p>\n");
+}
+
+#[test]
+fn test_unknown() {
+    emoji_test!(
+        "At 10:30:45, :notanemoji:",
+        "<p>At 10:30:45, :notanemoji:</p>\n");
+}
+
+#[test]
+fn test_code() {
+    emoji_test!(
+        "`:smile:`\n\n    :smile:\n",
+        "<p><code>:smile:</code></p>\n\n<pre><code>:smile:\n</code></pre>\n");
+}
+
+#[test]
+fn test_html_image() {
+    let doc = Markdown::new("I :heart: it");
+    let mut renderer = Html::new(html::Flags::empty(), 0);
+    renderer.emoji(Style::Image(String::from("/emoji/{codepoint}.png")));
+
+    assert_eq!(
+        renderer.render(&doc).to_str().unwrap(),
+        "<p>I <img class=\"emoji\" title=\":heart:\" alt=\"\u{2764}\u{FE0F}\" src=\"/emoji/2764.png\"> it</p>\n");
+}
+
+#[test]
+fn test_html_through_reference() {
+    // the emphasis is rendered into a buffer of its own, and doesn't continue `:smi`
+    let doc = Markdown::new("I :heart_eyes: it, :smi*le*:");
+    let mut renderer = Html::new(html::Flags::empty(), 0);
+    renderer.emoji(Style::Unicode);
+
+    assert_eq!(
+        (&mut renderer).render(&doc).to_str().unwrap(),
+        "<p>I \u{1F60D} it, :smi<em>le</em>:</p>\n");
+}
+
+#[test]
+fn test_html_boxed() {
+    let doc = Markdown::new("Good job :+1:");
+    let mut html = Html::new(html::Flags::empty(), 0);
+    html.emoji(Style::Unicode);
+
+    let mut renderer: Box<dyn DynRender> = Box::new(html);
+    let mut output = hoedown::Buffer::new(64);
+    renderer.render_to(&doc, &mut output);
+
+    assert_eq!(output.to_str().unwrap(), "<p>Good job \u{1F44D}</p>\n");
+}
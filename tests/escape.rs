@@ -0,0 +1,25 @@
+extern crate hoedown;
+
+use hoedown::{Buffer, escape};
+
+#[test]
+fn test_html() {
+    let mut output = Buffer::new(64);
+    escape::html(b"<a href='/x'>\"&\"</a>", &mut output, false);
+
+    assert_eq!(output.to_str().unwrap(), "&lt;a href=&#39;/x&#39;&gt;&quot;&amp;&quot;&lt;/a&gt;");
+}
+
+#[test]
+fn test_html_secure() {
+    assert_eq!(escape::html_to_string("</script>", true), "&lt;&#47;script&gt;");
+}
+
+#[test]
+fn test_href() {
+    let mut output = Buffer::new(64);
+    escape::href(b"/a b/\xC3\xA9?x=1&y='2'", &mut output);
+
+    assert_eq!(output.to_str().unwrap(), "/a%20b/%C3%A9?x=1&amp;y=&#x27;2&#x27;");
+    assert_eq!(escape::href_to_string("<script>"), "%3Cscript%3E");
+}
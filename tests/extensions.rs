@@ -21,6 +21,151 @@ fn test_hard_wrap() {
         "<p><a href=\"https://github.com/\">https://github.com/</a></p>\n");
 }
 
+#[test]
+fn test_definition_lists() {
+    extensions_test!(DEFINITION_LISTS:
+        "Apple\n:   Pomaceous fruit.\n\nOrange\n:   Citrus fruit.\n\n    Grows on trees.\n",
+"<dl>
+<dt>Apple</dt>
+<dd>Pomaceous fruit.</dd>
+<dt>Orange</dt>
+<dd><p>Citrus fruit.</p>
+
+<p>Grows on trees.</p></dd>
+</dl>\n");
+
+    // not within html blocks or indented code
+    extensions_test!(DEFINITION_LISTS:
+        "<div>\n\nApple\n:   Pomaceous fruit.\n\n</div>\n\nText\n\n    Orange\n    :   Citrus fruit.\n",
+"<div>\n\nApple\n:   Pomaceous fruit.\n\n</div>
+
+<p>Text</p>
+
+<pre><code>Orange
+:   Citrus fruit.
+</code></pre>\n");
+}
+
+#[test]
+fn test_admonitions() {
+    extensions_test!(ADMONITIONS:
+        "!!! warning \"Be careful\"\n    Do not *do* it.\n",
+"<div class=\"admonition warning\">
+<p class=\"admonition-title\">Be careful</p>
+<p>Do not <em>do</em> it.</p>
+</div>\n");
+
+    extensions_test!(ADMONITIONS:
+        "> [!NOTE]\n> Read this.\n",
+"<div class=\"admonition note\">
+<p class=\"admonition-title\">Note</p>
+<p>Read this.</p>
+</div>\n");
+
+    // not within html blocks or indented code
+    extensions_test!(ADMONITIONS:
+        "<div>\n\n!!! note\n    Inside.\n\n</div>\n\nText\n\n    !!! note\n        Code.\n",
+"<div>\n\n!!! note\n    Inside.\n\n</div>
+
+<p>Text</p>
+
+<pre><code>!!! note
+    Code.
+</code></pre>\n");
+}
+
+#[test]
+fn test_wiki_links() {
+    extensions_test!(WIKI_LINKS:
+        "[[Page]] and [[Other Page|*other*]] but not `[[Code]]`",
+        "<p><a href=\"Page\">Page</a> and <a href=\"Other%20Page\"><em>other</em></a> but not <code>[[Code]]</code></p>\n");
+
+    extensions_test!(WIKI_LINKS:
+        "[[Page]]\n\n    [[Indented]] ](x)\n\n``a [[Span]] ](x) b``\n",
+"<p><a href=\"Page\">Page</a></p>
+
+<pre><code>[[Indented]] ](x)
+</code></pre>
+
+<p><code>a [[Span]] ](x) b</code></p>\n");
+
+    let mut renderer = html::Html::new(html::Flags::empty(), 0);
+    let doc =
+        Markdown::new("```\n[[Fenced|label]]\n```\n")
+        .extensions(hoedown::WIKI_LINKS | hoedown::FENCED_CODE);
+
+    assert_eq!(renderer.render(&doc).to_str().unwrap(), "<pre><code>[[Fenced|label]]\n</code></pre>\n");
+}
+
+#[test]
+fn test_literal_wiki_placeholders() {
+    extensions_test!(WIKI_LINKS:
+        "[x](hoedown-wiki:0) [x](hoedown-0-wiki:0) [[Page]]",
+        "<p><a href=\"hoedown-wiki:0\">x</a> <a href=\"hoedown-0-wiki:0\">x</a> <a href=\"Page\">Page</a></p>\n");
+}
+
+#[test]
+fn test_header_attributes() {
+    extensions_test!(HEADER_ATTRIBUTES:
+        "## Install ## {#install .important}\n\nSetup {.a .b data-x=\"1 & 2\"}\n=====\n\n# Plain {not attributes}\n",
+"<h2 id=\"install\" class=\"important\">Install</h2>
+
+<h1 class=\"a b\" data-x=\"1 &amp; 2\">Setup</h1>
+
+<h1>Plain {not attributes}</h1>\n");
+
+    // only headers under the active extensions, outside of html blocks
+    let mut renderer = html::Html::new(html::Flags::empty(), 0);
+    let doc =
+        Markdown::new("#tag {.x}\n\n## {#empty}\n\n<div>\n# Raw {#raw}\n</div>\n")
+        .extensions(hoedown::HEADER_ATTRIBUTES | hoedown::SPACE_HEADERS);
+
+    assert_eq!(renderer.render(&doc).to_str().unwrap(),
+"<p>#tag {.x}</p>
+
+<h2 id=\"empty\"></h2>
+
+<div>
+# Raw {#raw}
+</div>\n");
+}
+
+#[test]
+fn test_header_attributes_toc() {
+    let doc =
+        Markdown::new("# Install {#install}\n\n# Usage\n")
+        .extensions(hoedown::HEADER_ATTRIBUTES);
+
+    let mut toc = html::Html::toc(1);
+    let mut renderer = html::Html::new(html::Flags::empty(), 1);
+
+    let toc = toc.render(&doc);
+    let output = renderer.render(&doc);
+
+    assert!(toc.to_str().unwrap().contains("<a href=\"#toc_0\">Install</a>"));
+    assert_eq!(output.to_str().unwrap(),
+"<h1 id=\"install\"><a id=\"toc_0\"></a>Install</h1>
+
+<h1 id=\"toc_1\">Usage</h1>\n");
+}
+
+#[test]
+fn test_literal_placeholders() {
+    let mut renderer = html::Html::new(html::Flags::empty(), 0);
+    let doc =
+        Markdown::new("<!--hoedown-block:0-->\n\nApple\n:   Pomaceous fruit.\n\n\
+                       ## Title <hoedown-attributes-0>\n\n## Real {#real}\n")
+        .extensions(hoedown::DEFINITION_LISTS | hoedown::HEADER_ATTRIBUTES);
+
+    let output = renderer.render(&doc);
+    let output = output.to_str().unwrap();
+
+    assert!(output.starts_with("<!--hoedown-block:0-->\n"));
+    assert!(output.contains("<dl>\n<dt>Apple</dt>\n<dd>Pomaceous fruit.</dd>\n</dl>"));
+    assert!(output.contains("<h2>Title <hoedown-attributes-0></h2>"));
+    assert!(output.contains("<h2 id=\"real\">Real</h2>"));
+}
+
 #[test]
 fn test_fenced_code() {
     extensions_test!(FENCED_CODE:
@@ -140,6 +285,36 @@ fn test_tables() {
 </table>\n");
 }
 
+#[test]
+fn test_task_lists() {
+    extensions_test!(TASK_LISTS:
+        "- [ ] one\n- [x] two\n- three\n",
+"<ul>
+<li class=\"task-list-item\"><input type=\"checkbox\" class=\"task-list-item-checkbox\" disabled> one</li>
+<li class=\"task-list-item\"><input type=\"checkbox\" class=\"task-list-item-checkbox\" disabled checked> two</li>
+<li>three</li>
+</ul>\n");
+}
+
+#[test]
+fn test_task_lists_source() {
+    // only markers that begin a list item in the source, not rendered text that looks like one
+    extensions_test!(TASK_LISTS:
+        "- `[ ]` code\n- [x] outer\n\n  - [ ] inner\n- <b>[x]</b> bold\n\nNot a list\n- [ ] item\n",
+"<ul>
+<li><code>[ ]</code> code</li>
+<li class=\"task-list-item\"><p><input type=\"checkbox\" class=\"task-list-item-checkbox\" disabled checked> outer</p>
+
+<ul>
+<li class=\"task-list-item\"><input type=\"checkbox\" class=\"task-list-item-checkbox\" disabled> inner</li>
+</ul></li>
+<li><p><b>[x]</b> bold</p></li>
+</ul>
+
+<p>Not a list
+- [ ] item</p>\n");
+}
+
 #[test]
 fn test_underline() {
     extensions_test!(UNDERLINE:
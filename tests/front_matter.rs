@@ -0,0 +1,98 @@
+extern crate hoedown;
+
+#[cfg(feature = "front-matter")]
+#[macro_use]
+extern crate serde_derive;
+
+use hoedown::Markdown;
+use hoedown::front_matter::Format;
+
+#[test]
+fn test_yaml() {
+    let (front, body) = Markdown::with_front_matter("---\ntitle: Test\n---\nBody.\n");
+    let front = front.unwrap();
+
+    assert_eq!(front.format, Format::Yaml);
+    assert_eq!(front.contents.to_str().unwrap(), "title: Test\n");
+    assert_eq!(front.range, 4 .. 16);
+    assert_eq!(front.body_offset, 20);
+    assert_eq!(body.contents.to_str().unwrap(), "Body.\n");
+}
+
+#[test]
+fn test_yaml_dots() {
+    let (front, body) = Markdown::with_front_matter("---\ntitle: Test\n...\nBody.");
+
+    assert_eq!(front.unwrap().format, Format::Yaml);
+    assert_eq!(body.contents.to_str().unwrap(), "Body.");
+}
+
+#[test]
+fn test_toml() {
+    let (front, body) = Markdown::with_front_matter("+++\r\ntitle = \"Test\"\r\n+++\r\nBody.");
+    let front = front.unwrap();
+
+    assert_eq!(front.format, Format::Toml);
+    assert_eq!(front.contents.to_str().unwrap(), "title = \"Test\"\r\n");
+    assert_eq!(body.contents.to_str().unwrap(), "Body.");
+}
+
+#[test]
+fn test_unterminated() {
+    let (front, body) = Markdown::with_front_matter("---\ntitle: Test\n\nBody.");
+
+    assert!(front.is_none());
+    assert_eq!(body.contents.to_str().unwrap(), "---\ntitle: Test\n\nBody.");
+}
+
+#[test]
+fn test_not_at_start() {
+    let (front, _) = Markdown::with_front_matter("Body.\n\n---\ntitle: Test\n---\n");
+
+    assert!(front.is_none());
+}
+
+#[test]
+fn test_keeps_extensions() {
+    let (_, body) =
+        Markdown::new("---\n---\n~~gone~~")
+        .extensions(hoedown::STRIKETHROUGH)
+        .max_nesting(4)
+        .split_front_matter();
+
+    assert_eq!(body.extensions, hoedown::STRIKETHROUGH);
+    assert_eq!(body.max_nesting, 4);
+    assert_eq!(body.contents.to_str().unwrap(), "~~gone~~");
+}
+
+#[cfg(feature = "front-matter")]
+#[test]
+fn test_deserialize() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Meta {
+        title: String,
+        tags: Vec<String>,
+    }
+
+    let expected = Meta {
+        title: String::from("Test"),
+        tags: vec![String::from("one"), String::from("two")],
+    };
+
+    let (yaml, _) = Markdown::with_front_matter("---\ntitle: Test\ntags: [one, two]\n---\n");
+    assert_eq!(yaml.unwrap().deserialize::<Meta>().unwrap(), expected);
+
+    let (toml, _) = Markdown::with_front_matter("+++\ntitle = \"Test\"\ntags = [\"one\", \"two\"]\n+++\n");
+    assert_eq!(toml.unwrap().deserialize::<Meta>().unwrap(), expected);
+}
+
+#[test]
+fn test_title() {
+    let (yaml, _) = Markdown::with_front_matter("---\ntitles: No\ntitle: \"Quoted: Title\"\n---\n");
+    let (toml, _) = Markdown::with_front_matter("+++\ntitle = 'Test'\n+++\n");
+    let (none, _) = Markdown::with_front_matter("---\nauthor: Someone\n---\n");
+
+    assert_eq!(yaml.unwrap().title(), Some(String::from("Quoted: Title")));
+    assert_eq!(toml.unwrap().title(), Some(String::from("Test")));
+    assert_eq!(none.unwrap().title(), None);
+}
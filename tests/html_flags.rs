@@ -41,3 +41,45 @@ fn test_xhtml() {
         "![spacer](spacer.gif)",
         "<p><img src=\"spacer.gif\" alt=\"spacer\"/></p>\n");
 }
+
+#[test]
+fn test_source_positions() {
+    let mut html_renderer = html::Html::new(html::SOURCE_POSITIONS, 0);
+    let doc =
+        Markdown::new("# Title\n\nSome text\n\n- one\n- two\n")
+        .extensions(hoedown::SOURCE_POSITIONS);
+
+    assert_eq!(
+        html_renderer.render(&doc).to_str().unwrap(),
+"<h1 data-sourcepos=\"1:1-1:7\">Title</h1>
+
+<p data-sourcepos=\"3:1-3:9\">Some text</p>
+
+<ul data-sourcepos=\"5:1-6:5\">
+<li>one</li>
+<li>two</li>
+</ul>\n");
+}
+
+#[test]
+fn test_source_positions_footnotes() {
+    let mut html_renderer = html::Html::new(html::SOURCE_POSITIONS, 0);
+    let doc =
+        Markdown::new("What you looking at? [^1]\n\n[^1]: Yeah, I'm talking to you pal.\n")
+        .extensions(hoedown::SOURCE_POSITIONS | hoedown::FOOTNOTES);
+
+    assert_eq!(
+        html_renderer.render(&doc).to_str().unwrap(),
+"<p data-sourcepos=\"1:1-1:25\">What you looking at? <sup id=\"fnref1\"><a href=\"#fn1\" rel=\"footnote\">1</a></sup></p>
+
+<div class=\"footnotes\">
+<hr>
+<ol>
+
+<li id=\"fn1\">
+<p>Yeah, I&#39;m talking to you pal.&nbsp;<a href=\"#fnref1\" rev=\"footnote\">&#8617;</a></p>
+</li>
+
+</ol>
+</div>\n");
+}
@@ -0,0 +1,52 @@
+extern crate hoedown;
+
+use std::path::Path;
+
+use hoedown::Markdown;
+use hoedown::link_check::{Checker, Reason};
+
+#[test]
+fn test_check() {
+    let mut checker = Checker::new(Path::new("tests/fixtures/link_check"));
+
+    checker.add_document("index.md", Markdown::new(
+        "# Index\n\n[guide](guide/setup.md#install) [bad](guide/setup.md#nope) [gone](missing.md)\n\n\
+         ![logo](/image.png) [web](https://example.com) [self](#top)\n\nNote[^1] and [^2].\n\n[^1]: Defined.\n")
+        .extensions(hoedown::FOOTNOTES | hoedown::HEADER_ATTRIBUTES));
+
+    checker.add_document("guide/setup.md", Markdown::new(
+        "# Install {#install}\n\n[back](../index.md#top) [up](../../image.png)\n\n# Top {#top}\n")
+        .extensions(hoedown::HEADER_ATTRIBUTES));
+
+    let broken: Vec<_> =
+        checker.check().into_iter()
+        .map(|b| (b.path.to_str().unwrap().to_string(), b.url, b.reason, b.position.start_line))
+        .collect();
+
+    assert_eq!(broken, vec![
+        (String::from("index.md"), String::from("guide/setup.md#nope"), Reason::MissingAnchor, 3),
+        (String::from("index.md"), String::from("missing.md"), Reason::MissingFile, 3),
+        (String::from("index.md"), String::from("#top"), Reason::MissingAnchor, 5),
+        (String::from("index.md"), String::from("2"), Reason::MissingFootnote, 7),
+        (String::from("guide/setup.md"), String::from("../index.md#top"), Reason::MissingAnchor, 3),
+        (String::from("guide/setup.md"), String::from("../../image.png"), Reason::OutsideRoot, 3),
+    ]);
+}
+
+#[test]
+fn test_missing_file() {
+    let mut checker = Checker::new(Path::new("tests/fixtures/link_check"));
+
+    checker.add_document("index.md", Markdown::new(
+        "[found](image.png) [lost](lost.md)\n\n![lost](images/lost.png)\n"));
+
+    let broken: Vec<_> =
+        checker.check().into_iter()
+        .map(|b| (b.url, b.reason, b.position.start_line, b.position.start_column))
+        .collect();
+
+    assert_eq!(broken, vec![
+        (String::from("lost.md"), Reason::MissingFile, 1, 27),
+        (String::from("images/lost.png"), Reason::MissingFile, 3, 9),
+    ]);
+}
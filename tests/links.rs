@@ -0,0 +1,64 @@
+extern crate hoedown;
+
+use hoedown::Markdown;
+use hoedown::renderer::AutoLink;
+use hoedown::links::Kind;
+
+#[test]
+fn test_links() {
+    let doc =
+        Markdown::new("[a *b*](x.md) ![img](y.png \"Y\")\n\nSee <http://z.com> or [ref][1]\n\n[1]: r.md\n")
+        .extensions(hoedown::AUTOLINK);
+
+    let links: Vec<_> =
+        doc.links().links.into_iter()
+        .map(|l| (l.kind, l.url, l.title, l.text, l.position.start_line, l.position.start_column))
+        .collect();
+
+    assert_eq!(links, vec![
+        (Kind::Link, String::from("x.md"), None, String::from("a b"), 1, 9),
+        (Kind::Image, String::from("y.png"), Some(String::from("Y")), String::from("img"), 1, 22),
+        (Kind::AutoLink(AutoLink::Normal), String::from("http://z.com"), None, String::from("http://z.com"), 3, 6),
+        // the url of a reference link isn't within its block
+        (Kind::Link, String::from("r.md"), None, String::from("ref"), 3, 1),
+    ]);
+}
+
+#[test]
+fn test_footnotes() {
+    let doc =
+        Markdown::new("Text[^1] and `[^code]`.\n\n[^1]: A note[^2].\n")
+        .extensions(hoedown::FOOTNOTES);
+
+    let links = doc.links();
+    let labels = |footnotes: &[hoedown::links::Footnote]| {
+        footnotes.iter().map(|f| (f.label.clone(), f.position.start_line, f.position.start_column)).collect::<Vec<_>>()
+    };
+
+    assert_eq!(labels(&links.footnote_references), vec![(String::from("1"), 1, 5), (String::from("2"), 3, 13)]);
+    assert_eq!(labels(&links.footnote_definitions), vec![(String::from("1"), 3, 1)]);
+
+    let doc =
+        Markdown::new("Text[^1].\n\n    [^indented]\n\n<div>\n[^html]\n</div>\n\n[^1]: A note.\n")
+        .extensions(hoedown::FOOTNOTES);
+
+    let links = doc.links();
+
+    assert_eq!(labels(&links.footnote_references), vec![(String::from("1"), 1, 5)]);
+    assert_eq!(labels(&links.footnote_definitions), vec![(String::from("1"), 9, 1)]);
+}
+
+#[test]
+fn test_footnote_links() {
+    let doc =
+        Markdown::new("Text[^1] and [a](a.md).\n\n[^1]: See [b](b.md).\n")
+        .extensions(hoedown::FOOTNOTES);
+
+    let links: Vec<_> =
+        doc.links().links.into_iter()
+        .map(|l| (l.url, l.position.start_line, l.position.start_column))
+        .collect();
+
+    // footnotes are rendered after the last block
+    assert_eq!(links, vec![(String::from("a.md"), 1, 18), (String::from("b.md"), 3, 15)]);
+}
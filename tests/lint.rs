@@ -0,0 +1,98 @@
+extern crate hoedown;
+
+use hoedown::Markdown;
+use hoedown::lint::{self, Linter, Rules};
+
+/// The rule, line and message of each diagnostic
+fn lint(linter: Linter, doc: Markdown) -> Vec<(Rules, usize, String)> {
+    linter.lint(&doc).into_iter()
+        .map(|d| (d.rule, d.position.start_line, d.message))
+        .collect()
+}
+
+#[test]
+fn test_headers() {
+    let linter = Linter::new(lint::HEADER_INCREMENT | lint::SINGLE_TITLE | lint::DUPLICATE_HEADERS);
+
+    assert_eq!(
+        lint(linter, Markdown::new("# One\n\n### *Three*\n\n# One\n")),
+        vec![
+            (lint::HEADER_INCREMENT, 3, String::from("header level skips from h1 to h3")),
+            (lint::SINGLE_TITLE, 5, String::from("multiple top-level headers")),
+            (lint::DUPLICATE_HEADERS, 5, String::from("duplicate header `One`")),
+        ]);
+}
+
+#[test]
+fn test_blocks_without_output() {
+    let linter = Linter::new(lint::HEADER_INCREMENT | lint::BARE_URLS);
+    let doc =
+        Markdown::new("# One\n\n[ref]: http://a.com\n\n### Three[^1]\n\n[^1]: See http://b.com\n")
+        .extensions(hoedown::FOOTNOTES);
+
+    // the reference definition renders nothing, and the footnote is rendered
+    // after the last block
+    assert_eq!(
+        lint(linter, doc),
+        vec![
+            (lint::BARE_URLS, 1, String::from("bare url `http://b.com`")),
+            (lint::HEADER_INCREMENT, 5, String::from("header level skips from h1 to h3")),
+        ]);
+}
+
+#[test]
+fn test_links() {
+    let linter = Linter::new(lint::EMPTY_LINKS | lint::IMAGE_ALT_TEXT | lint::BARE_URLS);
+    let doc = "Intro\n\n[](http://a.com) ![](b.png) see http://example.com, or [http://c.com](http://c.com)\n";
+
+    assert_eq!(
+        lint(linter.clone(), Markdown::new(doc)),
+        vec![
+            (lint::EMPTY_LINKS, 3, String::from("link without text or destination")),
+            (lint::IMAGE_ALT_TEXT, 3, String::from("image without alt text")),
+            (lint::BARE_URLS, 3, String::from("bare url `http://example.com`")),
+        ]);
+
+    assert_eq!(
+        lint(linter.clone(), Markdown::new(doc).extensions(hoedown::AUTOLINK)).len(),
+        2);
+
+    // the same url both within a link's text and outside of it
+    assert_eq!(
+        lint(linter, Markdown::new("[*http://c.com*](http://c.com) and http://c.com\n")),
+        vec![
+            (lint::BARE_URLS, 1, String::from("bare url `http://c.com`")),
+        ]);
+}
+
+#[test]
+fn test_lines() {
+    let linter = Linter::new(lint::FENCE_LANGUAGE | lint::LINE_LENGTH).max_line_length(20);
+    let doc =
+        Markdown::new("```\ncode\n```\n\n```rust\nfn main() {}\n```\n\nThis line is a little too long.\n")
+        .extensions(hoedown::FENCED_CODE);
+
+    let diagnostics = linter.lint(&doc);
+
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].rule, lint::FENCE_LANGUAGE);
+    assert_eq!(diagnostics[0].position.start_line, 1);
+    assert_eq!(diagnostics[1].rule, lint::LINE_LENGTH);
+    assert_eq!(diagnostics[1].message, "line is 31 characters long, exceeding 20");
+    assert_eq!((diagnostics[1].position.start_line, diagnostics[1].position.start_column), (9, 21));
+}
+
+#[test]
+fn test_line_length_code() {
+    let linter = Linter::new(lint::LINE_LENGTH).max_line_length(20);
+    let doc =
+        Markdown::new("```rust\nlet long = \"a string that goes on\";\n```\n\n    \
+                       let indented = \"also long enough\";\n\nThis line is a little too long.\n")
+        .extensions(hoedown::FENCED_CODE);
+
+    assert_eq!(
+        lint(linter, doc),
+        vec![
+            (lint::LINE_LENGTH, 7, String::from("line is 31 characters long, exceeding 20")),
+        ]);
+}
@@ -26,7 +26,7 @@ fn tidy(input: &str) -> String {
         .spawn()
         .unwrap();
 
-    process.stdin.as_mut().unwrap().write(input.as_bytes()).unwrap();
+    process.stdin.as_mut().unwrap().write_all(input.as_bytes()).unwrap();
 
     let output = process.wait_with_output().unwrap();
     String::from_utf8(output.stdout).unwrap()
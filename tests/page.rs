@@ -0,0 +1,34 @@
+extern crate hoedown;
+
+use hoedown::{Markdown, Html};
+use hoedown::renderer::html;
+use hoedown::renderer::page::Page;
+
+#[test]
+fn test_front_matter_title() {
+    let doc = Markdown::new("---\ntitle: Q&A\n---\n# Questions\n");
+    let page = Page::new().template("<title>{{title}}</title>\n{{body}}");
+
+    assert_eq!(
+        page.render(&mut Html::new(html::Flags::empty(), 0), &doc).to_str().unwrap(),
+        "<title>Q&amp;A</title>\n<h1>Questions</h1>\n");
+}
+
+#[test]
+fn test_toc() {
+    let doc = Markdown::new("# One *{{body}}*\n");
+    let page = Page::new().toc(1).template("<title>{{title}}</title>\n{{toc}}{{body}}{{unknown}}");
+
+    assert_eq!(
+        page.render(&mut Html::new(html::Flags::empty(), 1), &doc).to_str().unwrap(),
+"<title>One {{body}}</title>
+<nav class=\"toc\">
+<ul>
+<li>
+<a href=\"#toc_0\">One <em>{{body}}</em></a>
+</li>
+</ul>
+</nav>
+<h1 id=\"toc_0\">One <em>{{body}}</em></h1>
+{{unknown}}");
+}
@@ -1,7 +1,8 @@
 extern crate hoedown;
 
-use hoedown::Buffer;
+use hoedown::{Buffer, Markdown, Render};
 use hoedown::renderer::html;
+use hoedown::smartypants::{self, SmartyPants, Quotes, Locale, Dashes};
 
 macro_rules! smartypants_test {
     ($left:expr, $right:expr) => ({
@@ -48,3 +49,75 @@ fn test_en_dash() {
         "Non--zero.",
         "Non&ndash;zero.");
 }
+
+macro_rules! wrapper_test {
+    ($quotes:expr, $left:expr, $right:expr) => ({
+        let doc = Markdown::new($left);
+        let mut renderer = SmartyPants::new(html::Html::new(html::Flags::empty(), 0), $quotes);
+
+        assert_eq!(renderer.render(&doc).to_str().unwrap(), $right);
+    });
+}
+
+#[test]
+fn test_wrapper() {
+    wrapper_test!(Quotes::english(),
+        "\"Don't\" say 'it'... in the '90s --- or *\"here\"*",
+        "<p>\u{201C}Don\u{2019}t\u{201D} say \u{2018}it\u{2019}\u{2026} in the \u{2019}90s \u{2014} or <em>\u{201C}here\u{201D}</em></p>\n");
+}
+
+#[test]
+fn test_wrapper_skips_code() {
+    wrapper_test!(Quotes::french(),
+        "\"Oui\" `\"non\"`\n\n    \"code\" -- ...\n",
+        "<p>\u{AB}Oui\u{BB} <code>&quot;non&quot;</code></p>\n\n<pre><code>&quot;code&quot; -- ...\n</code></pre>\n");
+}
+
+#[test]
+fn test_wrapper_split_text() {
+    // hoedown reports the text on either side of the emphasis separately
+    wrapper_test!(Quotes::german(),
+        "\"*Wort*\" und '_x_'",
+        "<p>\u{201E}<em>Wort</em>\u{201C} und \u{201A}<em>x</em>\u{2018}</p>\n");
+}
+
+macro_rules! locale_test {
+    ($locale:expr, $left:expr, $right:expr) => ({
+        let input = Buffer::from($left);
+        let mut output = Buffer::new(64);
+
+        smartypants::html(&input, &mut output, &$locale);
+
+        assert_eq!(output.to_str().unwrap(), $right);
+    });
+}
+
+#[test]
+fn test_locale_quotes() {
+    locale_test!(Locale::german(),
+        "<p>&quot;Er sagte &#39;nein&#39;&quot; &amp; <a href=\"x\">ging</a>.</p>",
+        "<p>\u{201E}Er sagte \u{201A}nein\u{2018}\u{201C} &amp; <a href=\"x\">ging</a>.</p>");
+}
+
+#[test]
+fn test_locale_skips_code() {
+    locale_test!(Locale::english(),
+        "<p>It's <code>'raw' -- ...</code></p>\n<PRE>\"x\"</PRE> -- ...",
+        "<p>It\u{2019}s <code>'raw' -- ...</code></p>\n<PRE>\"x\"</PRE> \u{2013} \u{2026}");
+}
+
+#[test]
+fn test_french_spacing() {
+    locale_test!(Locale::french(),
+        "Quoi ! Vraiment? Il est 10:30 : voir http://example.com; fin",
+        "Quoi\u{202F}! Vraiment\u{202F}? Il est 10:30\u{A0}: voir http://example.com\u{202F}; fin");
+}
+
+#[test]
+fn test_dashes_and_ellipsis() {
+    let locale = Locale { dashes: Dashes::Inverted, ellipsis: false, .. Locale::english() };
+
+    locale_test!(locale,
+        "a -- b --- c ... d",
+        "a \u{2014} b \u{2013} c ... d");
+}
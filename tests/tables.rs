@@ -0,0 +1,83 @@
+extern crate hoedown;
+
+use hoedown::Markdown;
+use hoedown::renderer::html;
+use hoedown::renderer::table::Alignment;
+use hoedown::tables::{self, Table};
+
+fn strings(cells: &[&str]) -> Vec<String> {
+    cells.iter().map(|&c| String::from(c)).collect()
+}
+
+fn table() -> Table {
+    Table {
+        headers: strings(&["key", "description"]),
+        alignments: vec![Alignment::Left, Alignment::None],
+        rows: vec![
+            strings(&["a,b", "say \"hi\""]),
+            strings(&["tab", "one\ttwo\\"]),
+        ],
+    }
+}
+
+#[test]
+fn test_extract() {
+    let doc = Markdown::new("\
+Errors:
+
+| Code | Name | Meaning |
+|:----:|------|--------:|
+| 1 | `E_ONE` | *very* [bad](x) |
+| 2 |
+
+| only |
+|------|
+| row |
+");
+
+    assert_eq!(doc.tables(), vec![
+        Table {
+            headers: strings(&["Code", "Name", "Meaning"]),
+            alignments: vec![Alignment::Center, Alignment::None, Alignment::Right],
+            rows: vec![
+                strings(&["1", "E_ONE", "very bad"]),
+                strings(&["2", "", ""]),
+            ],
+        },
+        Table {
+            headers: strings(&["only"]),
+            alignments: vec![Alignment::None],
+            rows: vec![strings(&["row"])],
+        },
+    ]);
+}
+
+#[test]
+fn test_extract_entities() {
+    let doc = Markdown::new("| Company | Note |\n|---|---|\n| AT&amp;T | &lt;&#8364;&#x41;&gt; &bogus; |\n");
+
+    assert_eq!(doc.tables()[0].rows, vec![strings(&["AT&T", "<\u{20AC}A> &bogus;"])]);
+}
+
+#[test]
+fn test_extract_html() {
+    let doc = Markdown::new("| a & b |\n|---|\n| `<x>` |\n");
+    let tables = tables::extract_html(&doc, html::Flags::empty());
+
+    assert_eq!(tables[0].headers, strings(&["a &amp; b"]));
+    assert_eq!(tables[0].rows, vec![strings(&["<code>&lt;x&gt;</code>"])]);
+}
+
+#[test]
+fn test_csv() {
+    assert_eq!(
+        table().to_csv(),
+        "key,description\r\n\"a,b\",\"say \"\"hi\"\"\"\r\ntab,one\ttwo\\\r\n");
+}
+
+#[test]
+fn test_json() {
+    assert_eq!(
+        table().to_json(),
+        r#"{"headers":["key","description"],"alignments":["left",null],"rows":[["a,b","say \"hi\""],["tab","one\ttwo\\"]]}"#);
+}
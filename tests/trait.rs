@@ -3,6 +3,7 @@ extern crate hoedown;
 
 use hoedown::{Markdown, Buffer, Render, Wrapper, Html};
 use hoedown::renderer;
+use hoedown::renderer::dynamic::DynRender;
 
 use std::io::Write;
 
@@ -38,63 +39,182 @@ impl Render for BlockRenderer {
         let s = format!("[CODE_BLOCK language={}] {}",
                         language.and_then(|l| l.to_str().ok()).unwrap_or(""),
                         input.and_then(|i| i.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
     }
 
     fn html_block(&mut self, output: &mut Buffer, input: Option<&Buffer>) {
         let s = format!("[HTML_BLOCK] {}", input.and_then(|i| i.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
     }
 
     fn quote_block(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
         let s = format!("[QUOTE_BLOCK] {}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
     }
 
     fn header(&mut self, output: &mut Buffer, input: Option<&Buffer>, level: i32) {
         let s = format!("[HEADER level={}] {}", level, input.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
     }
 
     fn horizontal_rule(&mut self, output: &mut Buffer) {
-        output.write(b"[HORIZONTAL_RULE]").unwrap();
+        output.write_all(b"[HORIZONTAL_RULE]").unwrap();
     }
 
     fn list(&mut self, output: &mut Buffer, input: Option<&Buffer>, list_flags: renderer::list::List) {
         let is_ordered = list_flags.intersects(renderer::list::ORDERED);
         let s = format!("[LIST ordered={}]\n{}", is_ordered, input.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
     }
 
     fn list_item(&mut self, output: &mut Buffer, input: Option<&Buffer>, list_flags: renderer::list::List) {
         let is_ordered = list_flags.intersects(renderer::list::ORDERED);
         let s = format!("[LISTITEM ordered={}] {}", is_ordered, input.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
     }
 
     fn table(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
         let s = format!("[TABLE]\n{}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
     }
 
     fn table_header(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
         let s = format!("[TABLE_HEADER]{}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
     }
 
     fn table_body(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
         let s = format!("\n[TABLE_BODY]{}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
     }
 
-    fn table_row(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+    fn table_row(&mut self, output: &mut Buffer, content: Option<&Buffer>, _columns: usize) {
         let s = format!("\n[TABLE_ROW]\n{}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
     }
 
-    fn table_cell(&mut self, output: &mut Buffer, content: Option<&Buffer>, _flags: renderer::Table) {
+    fn table_cell(&mut self, output: &mut Buffer, content: Option<&Buffer>, _flags: renderer::Table, _column: usize, _columns: usize) {
         let s = format!("[TABLE_CELL text={}]", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
+    }
+}
+
+struct TableRenderer;
+
+impl Render for TableRenderer {
+    fn table(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        content.map(|c| output.pipe(c));
+    }
+
+    fn table_header(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        content.map(|c| output.pipe(c));
+    }
+
+    fn table_body(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        content.map(|c| output.pipe(c));
+    }
+
+    fn table_row(&mut self, output: &mut Buffer, content: Option<&Buffer>, columns: usize) {
+        let s = format!("[TABLE_ROW columns={}]{}\n", columns, content.and_then(|b| b.to_str().ok()).unwrap_or(""));
+        output.write_all(s.as_bytes()).unwrap();
+    }
+
+    fn table_cell(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: renderer::Table, column: usize, columns: usize) {
+        let s = format!(" [TABLE_CELL {}/{} header={} alignment={:?}] {}",
+                        column, columns, flags.is_header(), flags.alignment(),
+                        content.and_then(|b| b.to_str().ok()).unwrap_or(""));
+        output.write_all(s.as_bytes()).unwrap();
+    }
+}
+
+struct DefinitionRenderer;
+
+impl Render for DefinitionRenderer {
+    fn paragraph(&mut self, ob: &mut Buffer, content: Option<&Buffer>) {
+        content.map(|c| ob.pipe(c));
+    }
+
+    fn definition_list(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        let s = format!("[DEFINITION_LIST]\n{}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
+        output.write_all(s.as_bytes()).unwrap();
+    }
+
+    fn definition_term(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        let s = format!("[DEFINITION_TERM] {}\n", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
+        output.write_all(s.as_bytes()).unwrap();
+    }
+
+    fn definition_data(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
+        let s = format!("[DEFINITION_DATA] {}\n", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
+        output.write_all(s.as_bytes()).unwrap();
+    }
+}
+
+struct AdmonitionRenderer;
+
+impl Render for AdmonitionRenderer {
+    fn paragraph(&mut self, ob: &mut Buffer, content: Option<&Buffer>) {
+        content.map(|c| ob.pipe(c));
+    }
+
+    fn admonition(&mut self, output: &mut Buffer, content: Option<&Buffer>, kind: &Buffer, title: Option<&Buffer>) {
+        let s = format!("[ADMONITION kind={} title={:?}] {}",
+                        kind.to_str().unwrap(),
+                        title.map(|t| t.to_str().unwrap()),
+                        content.and_then(|b| b.to_str().ok()).unwrap_or(""));
+        output.write_all(s.as_bytes()).unwrap();
+    }
+}
+
+struct WikiRenderer;
+
+impl Render for WikiRenderer {
+    fn paragraph(&mut self, ob: &mut Buffer, content: Option<&Buffer>) {
+        content.map(|c| ob.pipe(c));
+    }
+
+    fn wiki_link(&mut self, output: &mut Buffer, target: &Buffer, label: Option<&Buffer>) -> bool {
+        let s = format!("[WIKI_LINK {}: {}]",
+                        target.to_str().unwrap(),
+                        label.and_then(|b| b.to_str().ok()).unwrap_or(""));
+        output.write_all(s.as_bytes()).unwrap();
+        true
+    }
+}
+
+struct HeaderRenderer;
+
+impl Render for HeaderRenderer {
+    fn header(&mut self, output: &mut Buffer, content: Option<&Buffer>, level: i32) {
+        let s = format!("[HEADER level={}] {}\n", level, content.and_then(|b| b.to_str().ok()).unwrap_or(""));
+        output.write_all(s.as_bytes()).unwrap();
+    }
+
+    fn header_with_attributes(&mut self, output: &mut Buffer, content: Option<&Buffer>, level: i32, attributes: &renderer::Attributes) {
+        let s = format!("[HEADER level={} id={:?} classes={:?} pairs={:?}] {}\n",
+                        level, attributes.id, attributes.classes, attributes.pairs,
+                        content.and_then(|b| b.to_str().ok()).unwrap_or(""));
+        output.write_all(s.as_bytes()).unwrap();
+    }
+}
+
+struct PositionRenderer;
+
+impl Render for PositionRenderer {
+    fn header(&mut self, ob: &mut Buffer, content: Option<&Buffer>, _level: i32) {
+        content.map(|c| ob.pipe(c));
+    }
+
+    fn paragraph(&mut self, ob: &mut Buffer, content: Option<&Buffer>) {
+        content.map(|c| ob.pipe(c));
+    }
+
+    fn positioned_block(&mut self, output: &mut Buffer, content: Option<&Buffer>, position: &renderer::SourcePosition) {
+        let s = format!("[BLOCK {}:{}-{}:{}] {}\n",
+                        position.start_line, position.start_column,
+                        position.end_line, position.end_column,
+                        content.and_then(|b| b.to_str().ok()).unwrap_or(""));
+        output.write_all(s.as_bytes()).unwrap();
     }
 }
 
@@ -106,11 +226,11 @@ impl Render for DocRenderer {
     }
 
     fn before_render(&mut self, output: &mut Buffer, _inline_render: bool) {
-        output.write(b"One.\n").unwrap();
+        output.write_all(b"One.\n").unwrap();
     }
 
     fn after_render(&mut self, output: &mut Buffer, _inline_render: bool) {
-        output.write(b"\nFive.").unwrap();
+        output.write_all(b"\nFive.").unwrap();
     }
 }
 
@@ -123,17 +243,17 @@ impl Render for FootnotesRenderer {
 
     fn footnotes(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
         let s = format!("[FOOTNOTES]\n{}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
     }
 
     fn footnote_definition(&mut self, output: &mut Buffer, content: Option<&Buffer>, number: u32) {
         let s = format!("[FOOTNOTE_DEFINITION #{}] {}", number, content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
     }
 
     fn footnote_reference(&mut self, output: &mut Buffer, number: u32) -> bool {
         let s = format!("[FOOTNOTE_REFERENCE #{}]", number);
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
         true
     }
 }
@@ -147,12 +267,12 @@ impl Render for LowLevelRenderer {
 
     fn entity(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
         let s = format!("[ENTITY] {}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
     }
 
     fn normal_text(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
         let s = format!("[NORMAL_TEXT] {}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
     }
 }
 struct ParagraphRenderer;
@@ -160,7 +280,7 @@ struct ParagraphRenderer;
 impl Render for ParagraphRenderer {
     fn paragraph(&mut self, output: &mut Buffer, content: Option<&Buffer>) {
         let s = format!("[PARAGRAPH] {}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
     }
 }
 
@@ -172,37 +292,37 @@ impl Render for SpanRenderer {
     }
 
     fn line_break(&mut self, output: &mut Buffer) -> bool {
-        output.write(b"[LINE_BREAK]").unwrap();
+        output.write_all(b"[LINE_BREAK]").unwrap();
         true
     }
 
     fn autolink(&mut self, output: &mut Buffer, content: Option<&Buffer>, link_type: renderer::AutoLink) -> bool {
         let s = format!("[AUTOLINK type={:?}] {}", link_type, content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
         true
     }
 
     fn code_span(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
         let s = format!("[CODE_SPAN] {}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
         true
     }
 
     fn double_emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
         let s = format!("[DOUBLE_EMPHASIS] {}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
         true
     }
 
     fn emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
         let s = format!("[EMPHASIS] {}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
         true
     }
 
     fn highlight(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
         let s = format!("[HIGHLIGHT] {}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
         true
     }
 
@@ -211,7 +331,7 @@ impl Render for SpanRenderer {
                         link.and_then(|b| b.to_str().ok()).unwrap_or(""),
                         title.and_then(|b| b.to_str().ok()).unwrap_or(""),
                         alt.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
         true
     }
 
@@ -220,41 +340,54 @@ impl Render for SpanRenderer {
                         link.and_then(|b| b.to_str().ok()).unwrap_or(""),
                         title.and_then(|b| b.to_str().ok()).unwrap_or(""),
                         content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
         true
     }
 
     fn quote_span(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
         let s = format!("[QUOTE] {}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
         true
     }
 
     fn html_span(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
         let s = format!("[HTML_SPAN] {}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
         true
     }
 
     fn strikethrough(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
         let s = format!("[STRIKETHROUGH] {}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
         true
     }
 
     fn superscript(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
         let s = format!("[SUPERSCRIPT] {}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
         true
     }
 
     fn triple_emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
         let s = format!("[TRIPLE_EMPHASIS] {}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
         true
     }
 }
 
+struct TaskRenderer;
+
+impl Render for TaskRenderer {
+    fn list(&mut self, output: &mut Buffer, content: Option<&Buffer>, _flags: renderer::list::List) {
+        content.map(|c| output.pipe(c));
+    }
+
+    fn list_item(&mut self, output: &mut Buffer, content: Option<&Buffer>, flags: renderer::list::List) {
+        let s = format!("[LISTITEM checked={:?}] {}", flags.checked(), content.and_then(|b| b.to_str().ok()).unwrap_or(""));
+        output.write_all(s.as_bytes()).unwrap();
+    }
+}
+
 struct UnderlineRenderer;
 
 impl Render for UnderlineRenderer {
@@ -264,7 +397,7 @@ impl Render for UnderlineRenderer {
 
     fn underline(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
         let s = format!("[UNDERLINE] {}", content.and_then(|b| b.to_str().ok()).unwrap_or(""));
-        output.write(s.as_bytes()).unwrap();
+        output.write_all(s.as_bytes()).unwrap();
         true
     }
 }
@@ -334,6 +467,59 @@ fn test_blockquote() {
         "[QUOTE_BLOCK] Echo.");
 }
 
+#[test]
+fn test_definition_list() {
+    renderer_test!(DefinitionRenderer, hoedown::DEFINITION_LISTS =>
+        "Term\nOther term\n: One\n: _Two_",
+"[DEFINITION_LIST]
+[DEFINITION_TERM] Term
+[DEFINITION_TERM] Other term
+[DEFINITION_DATA] One
+[DEFINITION_DATA] _Two_\n");
+}
+
+#[test]
+fn test_definition_list_fenced() {
+    renderer_test!(Html::new(renderer::html::Flags::empty(), 0), hoedown::FENCED_CODE | hoedown::DEFINITION_LISTS =>
+        "```\nTerm\n: Definition\n```",
+        "<pre><code>Term\n: Definition\n</code></pre>\n");
+}
+
+#[test]
+fn test_admonition() {
+    renderer_test!(AdmonitionRenderer, hoedown::ADMONITIONS =>
+        "> [!Warning]\n> Careful",
+        "[ADMONITION kind=warning title=None] Careful");
+
+    renderer_test!(AdmonitionRenderer, hoedown::ADMONITIONS =>
+        "!!! tip \"A *tip*\"\n    Read this.",
+        "[ADMONITION kind=tip title=Some(\"A *tip*\")] Read this.");
+}
+
+#[test]
+fn test_wiki_link() {
+    renderer_test!(WikiRenderer, hoedown::WIKI_LINKS =>
+        "[[Page]], [[ Other | *label* ]], ![[Embed]], \\[[Escaped]]",
+        "[WIKI_LINK Page: Page], [WIKI_LINK Other: *label*], ![[Embed]], [[Escaped]]");
+}
+
+#[test]
+fn test_header_attributes() {
+    renderer_test!(HeaderRenderer, hoedown::HEADER_ATTRIBUTES =>
+        "# One {#one .first key=value}\n\n# Two",
+"[HEADER level=1 id=Some(\"one\") classes=[\"first\"] pairs=[(\"key\", \"value\")]] One
+[HEADER level=1] Two\n");
+}
+
+#[test]
+fn test_source_positions() {
+    renderer_test!(PositionRenderer, hoedown::SOURCE_POSITIONS =>
+        "# Title\n\nSome text\nover two lines\n",
+"[BLOCK 1:1-1:7] Title
+[BLOCK 3:1-4:14] Some text
+over two lines\n");
+}
+
 #[test]
 fn test_footnotes() {
     renderer_test!(FootnotesRenderer, hoedown::FOOTNOTES =>
@@ -376,6 +562,15 @@ fn test_list_unordered() {
 [LISTITEM ordered=false] Five\n");
 }
 
+#[test]
+fn test_list_task() {
+    renderer_test!(TaskRenderer, hoedown::TASK_LISTS =>
+        "- [ ] One\n- [x] Two\n- Five",
+"[LISTITEM checked=Some(false)] One
+[LISTITEM checked=Some(true)] Two
+[LISTITEM checked=None] Five\n");
+}
+
 #[test]
 fn test_paragraph() {
     renderer_test!(ParagraphRenderer =>
@@ -405,6 +600,23 @@ fn test_table() {
 [TABLE_CELL text=X][TABLE_CELL text=O][TABLE_CELL text=X]");
 }
 
+#[test]
+fn test_table_columns() {
+    renderer_test!(TableRenderer, hoedown::TABLES =>
+"| a | b | c |
+|:--|:-:|---|
+| 1 | 2 |
+| 3 | 4 | 5 |\n",
+
+"[TABLE_ROW columns=3] [TABLE_CELL 0/3 header=true alignment=Left] a \
+[TABLE_CELL 1/3 header=true alignment=Center] b [TABLE_CELL 2/3 header=true alignment=None] c
+[TABLE_ROW columns=3] [TABLE_CELL 0/3 header=false alignment=Left] 1 \
+[TABLE_CELL 1/3 header=false alignment=Center] 2 [TABLE_CELL 2/3 header=false alignment=None] 
+[TABLE_ROW columns=3] [TABLE_CELL 0/3 header=false alignment=Left] 3 \
+[TABLE_CELL 1/3 header=false alignment=Center] 4 [TABLE_CELL 2/3 header=false alignment=None] 5
+");
+}
+
 #[test]
 fn test_autolink() {
     renderer_test!(SpanRenderer, hoedown::AUTOLINK =>
@@ -509,3 +721,155 @@ fn test_low_level() {
         "&#9731;",
         "[NORMAL_TEXT] [ENTITY] &#9731;");
 }
+
+#[test]
+fn test_dyn_render() {
+    let doc = Markdown::new("some *emphasis*\n");
+    let mut renderers: Vec<Box<dyn DynRender>> = vec![
+        Box::new(Html::new(renderer::html::Flags::empty(), 0)),
+        Box::new(ParagraphRenderer),
+    ];
+
+    let outputs: Vec<String> =
+        renderers.iter_mut()
+        .map(|r| r.render(&doc).to_str().unwrap().to_string())
+        .collect();
+
+    assert_eq!(outputs, vec![
+        "<p>some <em>emphasis</em></p>\n",
+        "[PARAGRAPH] some *emphasis*",
+    ]);
+
+    fn render_with(mut renderer: &mut dyn DynRender, doc: &Markdown) -> Buffer {
+        renderer.render(doc)
+    }
+
+    let mut html = HtmlWrapper { html: Html::new(renderer::html::Flags::empty(), 0) };
+    assert_eq!(render_with(&mut html, &doc).to_str().unwrap(), "<p>some ~~emphasis~~</p>\n");
+}
+
+#[test]
+fn test_renderer_builder() {
+    use hoedown::renderer::builder::RendererBuilder;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let doc = Markdown::new("```rust\nfn main() {}\n```\n\nsee [docs](/docs)\n").extensions(hoedown::FENCED_CODE);
+    let blocks = Rc::new(Cell::new(0));
+    let counter = blocks.clone();
+
+    let mut renderer =
+        RendererBuilder::new(Html::new(renderer::html::Flags::empty(), 0))
+        .on_code_block(move |output, code, lang| {
+            counter.set(counter.get() + 1);
+            output.write_all(b"<pre data-lang=\"").unwrap();
+            lang.map(|l| output.pipe(l));
+            output.write_all(b"\">").unwrap();
+            code.map(|c| output.pipe(c));
+            output.write_all(b"</pre>\n").unwrap();
+        })
+        .on_link(|output, content, link, _title| {
+            output.write_all(b"<a href=\"https://example.com").unwrap();
+            link.map(|l| output.pipe(l));
+            output.write_all(b"\">").unwrap();
+            content.map(|c| output.pipe(c));
+            output.write_all(b"</a>").unwrap();
+            true
+        })
+        .build();
+
+    assert_eq!(renderer.render(&doc).to_str().unwrap(),
+               "<pre data-lang=\"rust\">fn main() {}\n</pre>\n\n\
+                <p>see <a href=\"https://example.com/docs\">docs</a></p>\n");
+    assert_eq!(blocks.get(), 1);
+}
+
+#[test]
+fn test_middleware_stack() {
+    use hoedown::renderer::middleware::{Stack, Middleware, Next};
+
+    struct Tag(&'static str);
+
+    impl Middleware for Tag {
+        fn emphasis(&mut self, next: &mut Next, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+            let mut tagged = Buffer::from(self.0);
+            content.map(|c| tagged.pipe(c));
+            next.emphasis(output, Some(&tagged))
+        }
+    }
+
+    struct Skip;
+
+    impl Middleware for Skip {
+        fn code_span(&mut self, _next: &mut Next, _output: &mut Buffer, _text: Option<&Buffer>) -> bool {
+            true
+        }
+    }
+
+    struct Emphasis;
+
+    impl Render for Emphasis {
+        fn emphasis(&mut self, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+            output.write_all(b"<em>").unwrap();
+            content.map(|c| output.pipe(c));
+            output.write_all(b"</em>").unwrap();
+            true
+        }
+    }
+
+    let mut stack = Stack::new(Emphasis).layer(Tag("a:"));
+    stack.push(Skip);
+    stack.push(Tag("b:"));
+    assert_eq!(stack.len(), 3);
+
+    let mut output = Buffer::new(64);
+    let input = Buffer::from("text");
+    assert!(Render::emphasis(&mut stack, &mut output, Some(&input)));
+    assert!(Render::code_span(&mut stack, &mut output, Some(&input)));
+    assert_eq!(output.to_str().unwrap(), "<em>b:a:text</em>");
+}
+
+#[test]
+fn test_middleware_stack_html() {
+    use hoedown::renderer::middleware::{Stack, Middleware, Next};
+
+    struct Absolute;
+
+    impl Middleware for Absolute {
+        fn link(&mut self, next: &mut Next, output: &mut Buffer, content: Option<&Buffer>, link: Option<&Buffer>, title: Option<&Buffer>) -> bool {
+            let mut absolute = Buffer::from("https://example.com");
+            link.map(|l| absolute.pipe(l));
+            next.link(output, content, Some(&absolute), title)
+        }
+    }
+
+    struct Shout;
+
+    impl Middleware for Shout {
+        fn normal_text(&mut self, next: &mut Next, output: &mut Buffer, text: Option<&Buffer>) {
+            let upper = text.map(|t| Buffer::from(&t.to_str().unwrap().to_uppercase()[..]));
+            next.normal_text(output, upper.as_ref())
+        }
+    }
+
+    struct Strike;
+
+    impl Middleware for Strike {
+        fn emphasis(&mut self, _next: &mut Next, output: &mut Buffer, content: Option<&Buffer>) -> bool {
+            output.write_all(b"<s>").unwrap();
+            content.map(|c| output.pipe(c));
+            output.write_all(b"</s>").unwrap();
+            true
+        }
+    }
+
+    let doc = Markdown::new("# Title\n\nsee *old* [docs](/docs)\n");
+    let mut stack =
+        Stack::new(Html::new(renderer::html::Flags::empty(), 0))
+        .layer(Absolute)
+        .layer(Shout)
+        .layer(Strike);
+
+    assert_eq!(stack.render(&doc).to_str().unwrap(),
+               "<h1>TITLE</h1>\n\n<p>SEE <s>OLD</s> <a href=\"https://example.com/docs\">DOCS</a></p>\n");
+}
@@ -0,0 +1,7 @@
+extern crate hoedown;
+
+#[test]
+fn test_version() {
+    let (major, _, _) = hoedown::version();
+    assert_eq!(major, hoedown::VERSION_MAJOR);
+}